@@ -0,0 +1,44 @@
+// src-tauri/benches/content_detector.rs
+//
+// Benchmarks `ContentDetector::detect_ranked` over a corpus of
+// representative clipboard samples, to catch regressions from the
+// single-pass feature scan it's built on (see `clipboard::content_detector`).
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use clip_mind_ai_lib::clipboard::ContentDetector;
+
+fn representative_samples() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("url", "https://github.com/microsoft/vscode/pull/12345"),
+        ("email", "jane.doe@example.com"),
+        ("phone", "+886-912-345-678"),
+        ("financial", "NT$12,345.00"),
+        ("datetime", "2024-01-15"),
+        (
+            "code",
+            "def fetch(url):\n    resp = requests.get(url)\n    return resp.json()\n\nfetch(\"https://x\")\n",
+        ),
+        ("address", "台北市信義區信義路五段7號35樓"),
+        (
+            "plain_text",
+            "Just a regular note I jotted down about tomorrow's meeting agenda.",
+        ),
+        // Ambiguous: phone-shaped digits that also read as a financial amount.
+        ("ambiguous_phone_or_financial", "NT$912345678"),
+    ]
+}
+
+fn bench_detect_ranked(c: &mut Criterion) {
+    let detector = ContentDetector::new();
+    let samples = representative_samples();
+
+    let mut group = c.benchmark_group("content_detector");
+    for (name, content) in &samples {
+        group.bench_function(*name, |b| {
+            b.iter(|| detector.detect_ranked(black_box(content)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_detect_ranked);
+criterion_main!(benches);
@@ -0,0 +1,242 @@
+// src-tauri/src/actions/command.rs
+//! User-defined shell-command actions, declared in a YAML manifest (see
+//! `load_command_actions`) instead of compiled in like the built-ins in
+//! `registry.rs`. Each configured action shells out to an external program
+//! with the clipboard content on stdin and `CLIPMIND_*` env vars carrying
+//! context, then returns its captured stdout as the result string - the
+//! same shape `ActionPlugin::execute` expects from every other action, so
+//! the suggestion list can grow without recompiling.
+
+use super::registry::{ActionPlugin, ActionRegistry};
+use crate::clipboard::types::{BasicContentType, ClipboardError};
+use async_trait::async_trait;
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tauri::State;
+
+/// One action declared in the manifest. `content_types` holds
+/// `BasicContentType` `Debug` names (e.g. `"Code"`, `"Url"`) this action is
+/// offered for; empty matches every type, mirroring `CopyMaskedAction`'s
+/// "show everywhere" catch-all in `registry.rs`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CommandActionConfig {
+    pub id: String,
+    pub label: String,
+    pub icon: String,
+    #[serde(default)]
+    pub hotkey: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub content_types: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandActionsManifest {
+    #[serde(default)]
+    actions: Vec<CommandActionConfig>,
+}
+
+/// Path precedence mirrors `AiEngine::load_config`: `CLIPMIND_ACTIONS_CONFIG`
+/// env var, else `../actions.yaml` relative to the `src-tauri` working dir.
+fn manifest_path() -> String {
+    env::var("CLIPMIND_ACTIONS_CONFIG").unwrap_or_else(|_| "../actions.yaml".to_string())
+}
+
+/// Loads the user-defined command actions, or an empty list if the manifest
+/// is missing/unparsable - a missing manifest just means no custom actions
+/// are registered, not a startup failure.
+pub fn load_command_actions() -> Vec<CommandActionConfig> {
+    let path = manifest_path();
+    match fs::read_to_string(&path) {
+        Ok(s) => match serde_yaml::from_str::<CommandActionsManifest>(&s) {
+            Ok(manifest) => manifest.actions,
+            Err(e) => {
+                warn!("Failed to parse command actions config ({}): {}", path, e);
+                Vec::new()
+            }
+        },
+        Err(_) => {
+            info!("No command actions config found at: {}", path);
+            Vec::new()
+        }
+    }
+}
+
+/// Runs `CommandActionConfig::command` against the clipboard content. Every
+/// configured action is one of these, parameterized only by its manifest
+/// entry.
+pub struct CommandAction {
+    config: CommandActionConfig,
+}
+
+impl CommandAction {
+    pub fn new(config: CommandActionConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl ActionPlugin for CommandAction {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        self.config.content_types.is_empty()
+            || self
+                .config
+                .content_types
+                .iter()
+                .any(|t| t == &format!("{:?}", content_type))
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let mut command = Command::new(&self.config.command);
+        command
+            .args(&self.config.args)
+            .env("CLIPMIND_CONTENT", content)
+            .env(
+                "CLIPMIND_CONTENT_TYPE",
+                params.get("content_type").map(String::as_str).unwrap_or(""),
+            )
+            .env(
+                "CLIPMIND_ITEM_ID",
+                params.get("item_id").map(String::as_str).unwrap_or(""),
+            )
+            .env(
+                "CLIPMIND_TIMESTAMP",
+                params.get("timestamp").map(String::as_str).unwrap_or(""),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let action_id = self.config.id.clone();
+        let content = content.to_string();
+
+        // `std::process::Command` blocks on spawn/wait, so this runs on the
+        // blocking pool rather than stalling the async runtime while the
+        // external program does its work.
+        tokio::task::spawn_blocking(move || -> Result<String, ClipboardError> {
+            let mut child = command.spawn().map_err(|e| {
+                ClipboardError::AccessError(format!("Failed to spawn `{}`: {}", action_id, e))
+            })?;
+
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin
+                    .write_all(content.as_bytes())
+                    .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+            }
+
+            let output = child
+                .wait_with_output()
+                .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+
+            if !output.status.success() {
+                return Err(ClipboardError::AccessError(format!(
+                    "Command `{}` exited with {}: {}",
+                    action_id,
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+
+            Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+        })
+        .await
+        .map_err(|e| ClipboardError::AccessError(e.to_string()))?
+    }
+}
+
+/// Counterpart to `popup::run_action`, for actions that need context beyond
+/// bare content: `content_type`/`item_id`/`timestamp` are threaded through
+/// as `params` so `CommandAction::execute` can export them as `CLIPMIND_*`
+/// env vars.
+#[tauri::command]
+pub async fn run_command_action(
+    registry: State<'_, ActionRegistry>,
+    action_id: String,
+    content: String,
+    content_type: String,
+    item_id: Option<String>,
+    timestamp: Option<String>,
+) -> Result<String, String> {
+    let mut params = HashMap::new();
+    params.insert("content_type".to_string(), content_type);
+    if let Some(id) = item_id {
+        params.insert("item_id".to_string(), id);
+    }
+    if let Some(ts) = timestamp {
+        params.insert("timestamp".to_string(), ts);
+    }
+
+    registry
+        .execute(&action_id, &content, &params)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo_config(content_types: Vec<String>) -> CommandActionConfig {
+        CommandActionConfig {
+            id: "test_echo".to_string(),
+            label: "Test Echo".to_string(),
+            icon: "🔧".to_string(),
+            hotkey: "9".to_string(),
+            command: "cat".to_string(),
+            args: Vec::new(),
+            content_types,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_pipes_content_through_stdin() {
+        let action = CommandAction::new(echo_config(Vec::new()));
+        let result = action.execute("hello from clipboard", &HashMap::new()).await;
+        assert_eq!(result.unwrap(), "hello from clipboard");
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_nonzero_exit() {
+        let mut config = echo_config(Vec::new());
+        config.command = "false".to_string();
+        let action = CommandAction::new(config);
+        let result = action.execute("content", &HashMap::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_can_handle_empty_filter_matches_every_type() {
+        let action = CommandAction::new(echo_config(Vec::new()));
+        assert!(action.can_handle(&BasicContentType::Code));
+        assert!(action.can_handle(&BasicContentType::Url));
+    }
+
+    #[test]
+    fn test_can_handle_respects_content_type_filter() {
+        let action = CommandAction::new(echo_config(vec!["Code".to_string()]));
+        assert!(action.can_handle(&BasicContentType::Code));
+        assert!(!action.can_handle(&BasicContentType::Url));
+    }
+
+    #[test]
+    fn test_load_command_actions_missing_manifest_is_empty() {
+        std::env::set_var("CLIPMIND_ACTIONS_CONFIG", "/nonexistent/clipmind_actions.yaml");
+        assert!(load_command_actions().is_empty());
+        std::env::remove_var("CLIPMIND_ACTIONS_CONFIG");
+    }
+}
@@ -0,0 +1,9 @@
+// src-tauri/src/actions/mod.rs
+pub mod command;
+pub mod placement;
+pub mod popup;
+pub mod registry;
+pub mod session_store;
+
+pub use registry::{ActionPlugin, ActionRegistry};
+pub use session_store::SessionStore;
@@ -0,0 +1,104 @@
+// src-tauri/src/actions/placement.rs
+//! Pure geometry for anchoring the popup near the cursor without letting it
+//! spill off the active monitor. Kept free of any Tauri types so the
+//! flip/clamp logic can be unit tested without a running window; `popup.rs`
+//! is the only place that touches real cursor/monitor queries.
+
+/// Compute the popup's top-left corner given the cursor position, the
+/// bounds of the monitor it's on (`(x, y, width, height)`), and the
+/// popup's size. Opens below-right of the cursor by default; flips to the
+/// opposite side of whichever edge(s) it would spill past, then clamps
+/// into the monitor bounds as a last resort (covers a popup bigger than
+/// the monitor, or a flip that still doesn't fit).
+pub fn place_near_cursor(
+    cursor: (f64, f64),
+    monitor_bounds: (f64, f64, f64, f64),
+    popup_size: (f64, f64),
+    offset: f64,
+) -> (f64, f64) {
+    let (cursor_x, cursor_y) = cursor;
+    let (area_x, area_y, area_w, area_h) = monitor_bounds;
+    let (popup_w, popup_h) = popup_size;
+
+    let mut x = cursor_x + offset;
+    let mut y = cursor_y + offset;
+
+    if x + popup_w > area_x + area_w {
+        x = cursor_x - offset - popup_w;
+    }
+    if y + popup_h > area_y + area_h {
+        y = cursor_y - offset - popup_h;
+    }
+
+    x = x.clamp(area_x, (area_x + area_w - popup_w).max(area_x));
+    y = y.clamp(area_y, (area_y + area_h - popup_h).max(area_y));
+
+    (x, y)
+}
+
+/// Slide a `size`-sized rectangle at `pos` back fully inside `bounds`
+/// (`(x, y, width, height)`) without otherwise moving it. Used when a
+/// window grows in place (`resize_popup_to_content`) and just needs to
+/// stay on-monitor, rather than needing the cursor-anchored flip logic
+/// `place_near_cursor` does for the initial placement.
+pub fn clamp_into_bounds(pos: (f64, f64), size: (f64, f64), bounds: (f64, f64, f64, f64)) -> (f64, f64) {
+    let (x, y) = pos;
+    let (w, h) = size;
+    let (bx, by, bw, bh) = bounds;
+
+    let clamped_x = x.clamp(bx, (bx + bw - w).max(bx));
+    let clamped_y = y.clamp(by, (by + bh - h).max(by));
+
+    (clamped_x, clamped_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_places_below_right_of_cursor_by_default() {
+        let (x, y) = place_near_cursor((100.0, 100.0), (0.0, 0.0, 1920.0, 1080.0), (350.0, 400.0), 10.0);
+        assert_eq!((x, y), (110.0, 110.0));
+    }
+
+    #[test]
+    fn test_flips_left_when_spilling_off_right_edge() {
+        let (x, _) = place_near_cursor((1800.0, 100.0), (0.0, 0.0, 1920.0, 1080.0), (350.0, 400.0), 10.0);
+        assert!(x + 350.0 <= 1920.0);
+        assert!(x < 1800.0);
+    }
+
+    #[test]
+    fn test_flips_up_when_spilling_off_bottom_edge() {
+        let (_, y) = place_near_cursor((100.0, 1000.0), (0.0, 0.0, 1920.0, 1080.0), (350.0, 400.0), 10.0);
+        assert!(y + 400.0 <= 1080.0);
+        assert!(y < 1000.0);
+    }
+
+    #[test]
+    fn test_clamps_into_bounds_on_secondary_monitor() {
+        let (x, y) = place_near_cursor((1930.0, 1079.0), (1920.0, 0.0, 1280.0, 1080.0), (350.0, 400.0), 10.0);
+        assert!(x >= 1920.0 && x + 350.0 <= 1920.0 + 1280.0);
+        assert!(y >= 0.0 && y + 400.0 <= 1080.0);
+    }
+
+    #[test]
+    fn test_clamps_when_popup_larger_than_monitor() {
+        let (x, y) = place_near_cursor((10.0, 10.0), (0.0, 0.0, 200.0, 200.0), (350.0, 400.0), 10.0);
+        assert_eq!(x, 0.0);
+        assert_eq!(y, 0.0);
+    }
+
+    #[test]
+    fn test_clamp_into_bounds_leaves_in_range_position_untouched() {
+        let pos = clamp_into_bounds((500.0, 500.0), (350.0, 400.0), (0.0, 0.0, 1920.0, 1080.0));
+        assert_eq!(pos, (500.0, 500.0));
+    }
+
+    #[test]
+    fn test_clamp_into_bounds_slides_grown_window_back_onto_screen() {
+        let pos = clamp_into_bounds((1700.0, 900.0), (350.0, 400.0), (0.0, 0.0, 1920.0, 1080.0));
+        assert_eq!(pos, (1570.0, 680.0));
+    }
+}
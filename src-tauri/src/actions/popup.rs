@@ -1,6 +1,48 @@
 // src-tauri/src/actions/popup.rs
-use tauri::{AppHandle, WebviewUrl, WebviewWindowBuilder, Manager, Emitter};
+use super::placement::place_near_cursor;
+use super::registry::ActionRegistry;
+use super::session_store::SessionStore;
+use std::sync::Arc;
+use tauri::{AppHandle, State, WebviewUrl, WebviewWindowBuilder, Manager, Emitter};
 use serde_json::json;
+use log::warn;
+
+/// Gap (in physical pixels) kept between the cursor and the popup's edge.
+const POPUP_CURSOR_OFFSET: f64 = 12.0;
+
+/// Top-left corner for a `popup_w` x `popup_h` window anchored near the
+/// cursor, clamped to the monitor the cursor is currently on. Falls back to
+/// the cursor position itself (or the old fixed corner, if even the cursor
+/// can't be queried) rather than failing the whole popup.
+fn compute_popup_position(app: &AppHandle, popup_w: f64, popup_h: f64) -> (f64, f64) {
+    let cursor = match app.cursor_position() {
+        Ok(cursor) => cursor,
+        Err(e) => {
+            warn!("Failed to read cursor position, using fallback popup position: {}", e);
+            return (900.0, 100.0);
+        }
+    };
+
+    let monitor = app
+        .monitor_from_point(cursor.x, cursor.y)
+        .ok()
+        .flatten()
+        .or_else(|| app.primary_monitor().ok().flatten());
+
+    match monitor {
+        Some(monitor) => {
+            let position = monitor.position();
+            let size = monitor.size();
+            place_near_cursor(
+                (cursor.x, cursor.y),
+                (position.x as f64, position.y as f64, size.width as f64, size.height as f64),
+                (popup_w, popup_h),
+                POPUP_CURSOR_OFFSET,
+            )
+        }
+        None => (cursor.x + POPUP_CURSOR_OFFSET, cursor.y + POPUP_CURSOR_OFFSET),
+    }
+}
 
 #[tauri::command]
 pub async fn show_popup_window(
@@ -43,6 +85,8 @@ pub async fn show_popup_window(
         tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
     }
 
+    let (popup_x, popup_y) = compute_popup_position(&app, fixed_width, dynamic_height);
+
     match WebviewWindowBuilder::new(
         &app,
         "popup",
@@ -56,7 +100,7 @@ pub async fn show_popup_window(
     .always_on_top(true)
     .skip_taskbar(true)
     .focused(true)
-    .position(900.0, 100.0) // Position on right side instead of center
+    .position(popup_x, popup_y) // Anchored near the cursor, clamped to its monitor
     .title("ClipMind Popup")
     .visible(true)
     .initialization_script(&format!(r#"
@@ -100,9 +144,12 @@ pub async fn show_popup_window(
 }
 
 #[tauri::command]
-pub async fn close_popup(app: AppHandle) -> Result<(), String> {
+pub async fn close_popup(
+    app: AppHandle,
+    session_store: State<'_, Arc<SessionStore>>,
+) -> Result<(), String> {
     println!("Executing close popup command");
-    
+
     if let Some(popup) = app.get_webview_window("popup") {
         println!("Destroying popup window");
         popup.destroy().map_err(|e| {
@@ -113,7 +160,11 @@ pub async fn close_popup(app: AppHandle) -> Result<(), String> {
     } else {
         println!("Popup window not found");
     }
-    
+
+    // Scratch files (e.g. from `open_vscode`) are only needed while the
+    // popup that spawned them is open.
+    session_store.cleanup();
+
     Ok(())
 }
 
@@ -138,231 +189,61 @@ pub async fn resize_popup_to_content(
         
         if let Ok(current_size) = window.inner_size() {
             let new_width = current_size.width as f64;
-            
+
             window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
                 width: new_width as u32,
                 height: new_height as u32,
             })).map_err(|e| e.to_string())?;
-            
+
             println!("Window resized to: {}x{}", new_width, new_height);
-        }
-    }
-    
-    Ok(())
-}
 
-#[tauri::command]
-pub async fn run_action(action_id: String, content: Option<String>) -> Result<String, String> {
-    println!("Executing action: {} with content: {:?}", action_id, content);
-    
-    match action_id.as_str() {
-        "search" => {
-            if let Some(content) = content {
-                let encoded_content = content
-                    .replace(" ", "+")
-                    .replace("&", "%26")
-                    .replace("=", "%3D")
-                    .replace("?", "%3F");
-                
-                let search_url = format!("https://www.google.com/search?q={}", encoded_content);
-                
-                #[cfg(target_os = "windows")]
-                {
-                    let _ = std::process::Command::new("cmd")
-                        .args(["/C", "start", &search_url])
-                        .spawn();
-                }
-                
-                #[cfg(target_os = "macos")]
-                {
-                    let _ = std::process::Command::new("open")
-                        .arg(&search_url)
-                        .spawn();
-                }
-                
-                #[cfg(target_os = "linux")]
-                {
-                    let _ = std::process::Command::new("xdg-open")
-                        .arg(&search_url)
-                        .spawn();
-                }
-                
-                Ok(format!("Open Google search: {}", content))
-            } else {
-                Err("no search context".to_string())
-            }
-        },
-        "translate" => {
-            Ok("Translation feature triggered".to_string())
-        },
-        "summarize" => {
-            Ok("Summarization feature triggered".to_string())
-        },
-        "open_browser" => {
-            if let Some(content) = content {
-                let url = if content.starts_with("http://") || content.starts_with("https://") {
-                    content
-                } else {
-                    format!("http://{}", content)
-                };
-                #[cfg(target_os = "windows")]
-                {
-                    let _ = std::process::Command::new("cmd")
-                        .args(["/C", "start", "", &url])
-                        .spawn();
-                }
-                #[cfg(target_os = "macos")]
-                {
-                    let _ = std::process::Command::new("open")
-                        .arg(&url)
-                        .spawn();
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    let _ = std::process::Command::new("xdg-open")
-                        .arg(&url)
-                        .spawn();
-                }
-                Ok(format!("Opened in browser: {}", url))
-            } else {
-                Err("No URL provided".to_string())
-            }
-        },
-        "open_vscode" => {
-            if let Some(content) = content {
-                use std::fs;
-                use std::io::Write;
-                let file_path = "clipmind_temp.txt";
-                if let Ok(mut file) = fs::File::create(file_path) {
-                    let _ = file.write_all(content.as_bytes());
-                }
+            // The new size may now spill off the monitor (e.g. a tall
+            // suggestions list growing past the bottom edge); slide the
+            // window back on-screen if so.
+            if let (Ok(current_position), Ok(Some(monitor))) =
+                (window.outer_position(), window.current_monitor())
+            {
+                let monitor_position = monitor.position();
+                let monitor_size = monitor.size();
+                let (clamped_x, clamped_y) = super::placement::clamp_into_bounds(
+                    (current_position.x as f64, current_position.y as f64),
+                    (new_width, new_height),
+                    (
+                        monitor_position.x as f64,
+                        monitor_position.y as f64,
+                        monitor_size.width as f64,
+                        monitor_size.height as f64,
+                    ),
+                );
 
-                #[cfg(target_os = "windows")]
-                {
-                    let _ = std::process::Command::new("cmd")
-                        .args(["/C", "start", "code", file_path])
-                        .spawn();
-                }
-                #[cfg(target_os = "macos")]
-                {
-                    let _ = std::process::Command::new("open")
-                        .args(["-a", "Visual Studio Code", file_path])
-                        .spawn();
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    let _ = std::process::Command::new("code")
-                        .arg(file_path)
-                        .spawn();
-                }
-                Ok(format!("Use VSCode to open: {}", file_path))
-            } else {
-                Err("no context".to_string())
-            }
-        },
-        "compose_email" => {
-            if let Some(content) = content {
-                #[cfg(target_os = "windows")]
-                {
-                    let mailto = format!("mailto:{}", content);
-                    let _ = std::process::Command::new("cmd")
-                        .args(["/C", "start", &mailto])
-                        .spawn();
-                }
-                #[cfg(target_os = "macos")]
-                {
-                    let mailto = format!("mailto:{}", content);
-                    let _ = std::process::Command::new("open")
-                        .arg(&mailto)
-                        .spawn();
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    let mailto = format!("mailto:{}", content);
-                    let _ = std::process::Command::new("xdg-open")
-                        .arg(&mailto)
-                        .spawn();
-                }
-                Ok(format!("Write email to: {}", content))
-            } else {
-                Err("no email address".to_string())
-            }
-        },
-        "open_maps" => {
-            if let Some(content) = content {
-                let url = format!("https://www.google.com/maps/search/{}", urlencoding::encode(&content));
-                #[cfg(target_os = "windows")]
-                {
-                    let _ = std::process::Command::new("cmd")
-                        .args(["/C", "start", "", &url])
-                        .spawn();
-                }
-                #[cfg(target_os = "macos")]
-                {
-                    let _ = std::process::Command::new("open")
-                        .arg(&url)
-                        .spawn();
-                }
-                #[cfg(target_os = "linux")]
+                if (clamped_x - current_position.x as f64).abs() > f64::EPSILON
+                    || (clamped_y - current_position.y as f64).abs() > f64::EPSILON
                 {
-                    let _ = std::process::Command::new("xdg-open")
-                        .arg(&url)
-                        .spawn();
+                    window
+                        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+                            x: clamped_x as i32,
+                            y: clamped_y as i32,
+                        }))
+                        .map_err(|e| e.to_string())?;
                 }
-                Ok(format!("open google map: {}", content))
-            } else {
-                Err("no address".to_string())
             }
-        },
-        "save_text" => {
-            if let Some(content) = content {
-                use std::fs;
-                let file_path = "clipmind_saved_text.txt";
-                if let Ok(_) = fs::write(file_path, &content) {
-                    Ok(format!("save file: {}", file_path))
-                } else {
-                    Err("save failed".to_string())
-                }
-            } else {
-                Err("no context".to_string())
-            }
-        },
-        _ => {
-            println!("Unimplemented action: {}", action_id);
-            Ok(format!("Action '{}' triggered but not yet implemented", action_id))
         }
     }
+    
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_search() {
-        let result = run_action("search".to_string(), Some("rust".to_string())).await;
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("Google search"));
-    }
-
-    #[tokio::test]
-    async fn test_open_browser() {
-        let result = run_action("open_browser".to_string(), Some("example.com".to_string())).await;
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("browser"));
-    }
-
-    #[tokio::test]
-    async fn test_open_vscode() {
-        let result = run_action("open_vscode".to_string(), Some("test content".to_string())).await;
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("VSCode"));
-    }
+#[tauri::command]
+pub async fn run_action(
+    registry: State<'_, ActionRegistry>,
+    action_id: String,
+    content: Option<String>,
+) -> Result<String, String> {
+    println!("Executing action: {} with content: {:?}", action_id, content);
 
-    #[tokio::test]
-    async fn test_save_text() {
-        let result = run_action("save_text".to_string(), Some("test".to_string())).await;
-        assert!(result.is_ok());
-        assert!(result.unwrap().contains("save file"));
-    }
+    let content = content.ok_or_else(|| "no context".to_string())?;
+    registry
+        .execute(&action_id, &content, &Default::default())
+        .await
+        .map_err(|e| e.to_string())
 }
\ No newline at end of file
@@ -0,0 +1,481 @@
+// src-tauri/src/actions/registry.rs
+//! Plugin-based replacement for the old `match action_id { ... }` in
+//! `popup::run_action`. Every action (search, open in VSCode, compose
+//! email, ...) is an `ActionPlugin` that registers itself with the
+//! `ActionRegistry` at startup; `run_action` just looks the plugin up by
+//! id and executes it. This is also what lets suggestion lists be built
+//! from "whichever plugins claim this content type" instead of a second
+//! hardcoded list, and leaves room for user-defined actions (loaded from a
+//! manifest: id, label, icon, hotkey, command template) to register
+//! alongside the built-ins without recompiling.
+
+use super::session_store::SessionStore;
+use crate::analyzer::Redactor;
+use crate::clipboard::types::{BasicContentType, ClipboardError};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[async_trait]
+pub trait ActionPlugin: Send + Sync {
+    /// Stable identifier, matched against the `action_id` the frontend sends.
+    fn id(&self) -> &str;
+
+    /// Whether this action is relevant for clips of this content type, used
+    /// to build per-type suggestion lists instead of showing every action.
+    fn can_handle(&self, content_type: &BasicContentType) -> bool;
+
+    /// Run the action against the given clipboard content.
+    async fn execute(
+        &self,
+        content: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError>;
+}
+
+/// Holds every registered plugin, keyed by id. Stored in Tauri managed
+/// state so commands can reach it via `tauri::State<ActionRegistry>`.
+pub struct ActionRegistry {
+    plugins: Mutex<HashMap<String, Arc<dyn ActionPlugin>>>,
+}
+
+impl ActionRegistry {
+    pub fn new() -> Self {
+        Self {
+            plugins: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The registry with every built-in action already registered.
+    /// `session_store` backs the actions (`open_vscode`, `save_text`) that
+    /// need a real file on disk, so they write under the app's data
+    /// directory instead of the process's current directory.
+    pub fn with_builtins(session_store: Arc<SessionStore>) -> Self {
+        let registry = Self::new();
+        registry.register(Arc::new(SearchAction));
+        registry.register(Arc::new(TranslateAction));
+        registry.register(Arc::new(SummarizeAction));
+        registry.register(Arc::new(OpenBrowserAction));
+        registry.register(Arc::new(OpenVscodeAction { session_store: session_store.clone() }));
+        registry.register(Arc::new(ComposeEmailAction));
+        registry.register(Arc::new(OpenMapsAction));
+        registry.register(Arc::new(SaveTextAction { session_store }));
+        registry.register(Arc::new(CopyMaskedAction));
+        registry
+    }
+
+    pub fn register(&self, plugin: Arc<dyn ActionPlugin>) {
+        self.plugins
+            .lock()
+            .unwrap()
+            .insert(plugin.id().to_string(), plugin);
+    }
+
+    /// Registers every action declared in the `actions::command` manifest
+    /// (see `command::load_command_actions`), on top of whatever's already
+    /// registered. A missing/empty manifest is a no-op, not an error.
+    pub fn register_command_actions(&self) {
+        for config in super::command::load_command_actions() {
+            self.register(Arc::new(super::command::CommandAction::new(config)));
+        }
+    }
+
+    /// Ids of every plugin that claims it can handle `content_type`, in the
+    /// order they were registered.
+    pub fn plugins_for(&self, content_type: &BasicContentType) -> Vec<String> {
+        let plugins = self.plugins.lock().unwrap();
+        plugins
+            .values()
+            .filter(|p| p.can_handle(content_type))
+            .map(|p| p.id().to_string())
+            .collect()
+    }
+
+    /// Look up and run `action_id`. Unknown ids are reported as an error
+    /// rather than silently no-op'ing, since an unregistered id usually
+    /// means a stale frontend build.
+    pub async fn execute(
+        &self,
+        action_id: &str,
+        content: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        // Clone the `Arc` out and drop the lock before awaiting, since the
+        // mutex guard isn't `Send` across an await point.
+        let plugin = {
+            let plugins = self.plugins.lock().unwrap();
+            plugins.get(action_id).cloned()
+        };
+
+        match plugin {
+            Some(plugin) => plugin.execute(content, params).await,
+            None => Err(ClipboardError::ParsingError(format!(
+                "Unknown action: {}",
+                action_id
+            ))),
+        }
+    }
+}
+
+/// Opens a URL/path with the OS's default handler (`start`/`open`/`xdg-open`).
+fn open_with_system_handler(target: &str) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = std::process::Command::new("cmd")
+            .args(["/C", "start", "", target])
+            .spawn();
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = std::process::Command::new("open").arg(target).spawn();
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let _ = std::process::Command::new("xdg-open").arg(target).spawn();
+    }
+}
+
+struct SearchAction;
+
+#[async_trait]
+impl ActionPlugin for SearchAction {
+    fn id(&self) -> &str {
+        "search"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        !matches!(content_type, BasicContentType::Code)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let encoded_content = content
+            .replace(" ", "+")
+            .replace("&", "%26")
+            .replace("=", "%3D")
+            .replace("?", "%3F");
+
+        let search_url = format!("https://www.google.com/search?q={}", encoded_content);
+        open_with_system_handler(&search_url);
+
+        Ok(format!("Open Google search: {}", content))
+    }
+}
+
+struct TranslateAction;
+
+#[async_trait]
+impl ActionPlugin for TranslateAction {
+    fn id(&self) -> &str {
+        "translate"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::PlainText)
+    }
+
+    async fn execute(
+        &self,
+        _content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        Ok("Translation feature triggered".to_string())
+    }
+}
+
+struct SummarizeAction;
+
+#[async_trait]
+impl ActionPlugin for SummarizeAction {
+    fn id(&self) -> &str {
+        "summarize"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::PlainText | BasicContentType::Url)
+    }
+
+    async fn execute(
+        &self,
+        _content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        Ok("Summarization feature triggered".to_string())
+    }
+}
+
+struct OpenBrowserAction;
+
+#[async_trait]
+impl ActionPlugin for OpenBrowserAction {
+    fn id(&self) -> &str {
+        "open_browser"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::Url)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let url = if content.starts_with("http://") || content.starts_with("https://") {
+            content.to_string()
+        } else {
+            format!("http://{}", content)
+        };
+        open_with_system_handler(&url);
+        Ok(format!("Opened in browser: {}", url))
+    }
+}
+
+struct OpenVscodeAction {
+    session_store: Arc<SessionStore>,
+}
+
+#[async_trait]
+impl ActionPlugin for OpenVscodeAction {
+    fn id(&self) -> &str {
+        "open_vscode"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::Code)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let path = self
+            .session_store
+            .write_scratch_file(content, ".txt")
+            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        let file_path = path.to_string_lossy().into_owned();
+
+        #[cfg(target_os = "windows")]
+        {
+            let _ = std::process::Command::new("cmd")
+                .args(["/C", "start", "code", &file_path])
+                .spawn();
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let _ = std::process::Command::new("open")
+                .args(["-a", "Visual Studio Code", &file_path])
+                .spawn();
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let _ = std::process::Command::new("code").arg(&file_path).spawn();
+        }
+
+        Ok(format!("Use VSCode to open: {}", file_path))
+    }
+}
+
+struct ComposeEmailAction;
+
+#[async_trait]
+impl ActionPlugin for ComposeEmailAction {
+    fn id(&self) -> &str {
+        "compose_email"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::Email)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let mailto = format!("mailto:{}", content);
+        open_with_system_handler(&mailto);
+        Ok(format!("Write email to: {}", content))
+    }
+}
+
+struct OpenMapsAction;
+
+#[async_trait]
+impl ActionPlugin for OpenMapsAction {
+    fn id(&self) -> &str {
+        "open_maps"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        matches!(content_type, BasicContentType::Address)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        let url = format!(
+            "https://www.google.com/maps/search/{}",
+            urlencoding::encode(content)
+        );
+        open_with_system_handler(&url);
+        Ok(format!("open google map: {}", content))
+    }
+}
+
+struct SaveTextAction {
+    session_store: Arc<SessionStore>,
+}
+
+#[async_trait]
+impl ActionPlugin for SaveTextAction {
+    fn id(&self) -> &str {
+        "save_text"
+    }
+
+    fn can_handle(&self, content_type: &BasicContentType) -> bool {
+        !matches!(content_type, BasicContentType::Code)
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        // A `dir` param lets the frontend honor a user-chosen save
+        // location; otherwise this falls back to the session's scratch
+        // directory instead of the process's current directory.
+        let result = match params.get("dir") {
+            Some(dir) => {
+                let path = std::path::Path::new(dir).join("clipmind_saved_text.txt");
+                self.session_store.write_to(&path, content)
+            }
+            None => self.session_store.write_scratch_file(content, ".txt"),
+        };
+
+        match result {
+            Ok(path) => Ok(format!("save file: {}", path.display())),
+            Err(e) => Err(ClipboardError::AccessError(e.to_string())),
+        }
+    }
+}
+
+/// Copies the content to the system clipboard with any detected PII/secrets
+/// masked out, for clips the user wants to share without their sensitive
+/// parts (e.g. pasting a card-less version of a receipt into a support chat).
+struct CopyMaskedAction;
+
+#[async_trait]
+impl ActionPlugin for CopyMaskedAction {
+    fn id(&self) -> &str {
+        "copy_masked"
+    }
+
+    fn can_handle(&self, _content_type: &BasicContentType) -> bool {
+        // PII/secrets can show up in any content type, so this is offered
+        // everywhere rather than gated to e.g. just `Financial`.
+        true
+    }
+
+    async fn execute(
+        &self,
+        content: &str,
+        _params: &HashMap<String, String>,
+    ) -> Result<String, ClipboardError> {
+        use arboard::Clipboard;
+
+        let redaction = Redactor::new().scan(content);
+        let masked = redaction.redacted_content.unwrap_or_else(|| content.to_string());
+
+        let mut clipboard = Clipboard::new()
+            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        clipboard
+            .set_text(&masked)
+            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+
+        Ok("Copied masked content to clipboard".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_registry() -> ActionRegistry {
+        let dir = std::env::temp_dir().join(format!(
+            "clipmind_registry_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        ActionRegistry::with_builtins(Arc::new(SessionStore::new(dir).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let registry = test_registry();
+        let result = registry.execute("search", "rust", &HashMap::new()).await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().contains("Google search"));
+    }
+
+    #[tokio::test]
+    async fn test_open_browser() {
+        let registry = test_registry();
+        let result = registry
+            .execute("open_browser", "example.com", &HashMap::new())
+            .await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().contains("browser"));
+    }
+
+    #[tokio::test]
+    async fn test_open_vscode() {
+        let registry = test_registry();
+        let result = registry
+            .execute("open_vscode", "test content", &HashMap::new())
+            .await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().contains("VSCode"));
+    }
+
+    #[tokio::test]
+    async fn test_save_text() {
+        let registry = test_registry();
+        let result = registry.execute("save_text", "test", &HashMap::new()).await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().contains("save file"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_masked_redacts_before_copying() {
+        let registry = test_registry();
+        let result = registry
+            .execute("copy_masked", "card 4111 1111 1111 1111", &HashMap::new())
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_action() {
+        let registry = test_registry();
+        let result = registry.execute("does_not_exist", "x", &HashMap::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_plugins_for_content_type() {
+        let registry = test_registry();
+        let url_actions = registry.plugins_for(&BasicContentType::Url);
+        assert!(url_actions.contains(&"open_browser".to_string()));
+        assert!(!url_actions.contains(&"open_vscode".to_string()));
+    }
+}
@@ -0,0 +1,115 @@
+// src-tauri/src/actions/session_store.rs
+//! Per-session scratch-file storage for actions that need a real file on
+//! disk (`open_vscode`, `save_text`). Replaces writing to fixed names
+//! (`clipmind_temp.txt`, `clipmind_saved_text.txt`) in the process's
+//! current directory, which collided across concurrent actions and left
+//! files behind in whatever folder the app happened to be launched from.
+//! Lives under the app's data directory instead, and tracks every path it
+//! hands out so a popup close can sweep the scratch ones away.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+pub struct SessionStore {
+    scratch_dir: PathBuf,
+    tracked: Mutex<Vec<PathBuf>>,
+    counter: AtomicU64,
+}
+
+impl SessionStore {
+    /// `scratch_dir` is created if missing (e.g. `<app_data_dir>/scratch`).
+    pub fn new(scratch_dir: PathBuf) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&scratch_dir)?;
+        Ok(Self {
+            scratch_dir,
+            tracked: Mutex::new(Vec::new()),
+            counter: AtomicU64::new(0),
+        })
+    }
+
+    /// Writes `content` to a freshly allocated, unique file in the scratch
+    /// directory and tracks it for cleanup, returning the real path.
+    pub fn write_scratch_file(&self, content: &str, suffix: &str) -> std::io::Result<PathBuf> {
+        let n = self.counter.fetch_add(1, Ordering::SeqCst);
+        let path = self
+            .scratch_dir
+            .join(format!("clip_{}_{}{}", std::process::id(), n, suffix));
+        std::fs::write(&path, content)?;
+        self.tracked.lock().unwrap().push(path.clone());
+        Ok(path)
+    }
+
+    /// Writes `content` to a caller-chosen path (e.g. a directory the user
+    /// picked for `save_text`) instead of an allocated scratch path. Still
+    /// tracked, so it's swept up if the popup closes before the user moves
+    /// or opens it elsewhere.
+    pub fn write_to(&self, path: &Path, content: &str) -> std::io::Result<PathBuf> {
+        std::fs::write(path, content)?;
+        self.tracked.lock().unwrap().push(path.to_path_buf());
+        Ok(path.to_path_buf())
+    }
+
+    /// Deletes every tracked file and forgets about them. Called when the
+    /// popup closes so scratch files don't accumulate across sessions.
+    pub fn cleanup(&self) {
+        let mut tracked = self.tracked.lock().unwrap();
+        for path in tracked.drain(..) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove session scratch file {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_session_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("clipmind_test_{}_{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_write_scratch_file_creates_unique_paths() {
+        let dir = temp_session_dir("unique");
+        let store = SessionStore::new(dir.clone()).unwrap();
+
+        let a = store.write_scratch_file("hello", ".txt").unwrap();
+        let b = store.write_scratch_file("hello", ".txt").unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "hello");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cleanup_removes_tracked_files() {
+        let dir = temp_session_dir("cleanup");
+        let store = SessionStore::new(dir.clone()).unwrap();
+
+        let path = store.write_scratch_file("content", ".txt").unwrap();
+        assert!(path.exists());
+
+        store.cleanup();
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_to_honors_caller_chosen_path() {
+        let dir = temp_session_dir("write_to");
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = SessionStore::new(dir.join("scratch")).unwrap();
+
+        let target = dir.join("chosen_name.txt");
+        let written = store.write_to(&target, "saved").unwrap();
+
+        assert_eq!(written, target);
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "saved");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
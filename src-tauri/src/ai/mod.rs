@@ -0,0 +1,307 @@
+// src-tauri/src/ai/mod.rs
+//! Local Ollama-backed action suggester.
+//!
+//! This used to be `src/bin/test_ollama.rs`, a throwaway binary that
+//! hard-coded the endpoint/model/prompt just to poke Ollama from the
+//! command line. `OllamaClient` is the real version: it's driven
+//! automatically off the `ClipboardChange` broadcast stream (see
+//! `spawn_driver`) instead of being invoked by hand, supports Ollama's
+//! streaming `/api/generate` mode so the UI can show suggestions as they're
+//! generated, and builds its prompt from the detector's `content_type`
+//! instead of a single fixed question.
+//!
+//! This is separate from `analyzer::ai_engine::AiEngine`, which talks to a
+//! Chat-Completions-style endpoint (Kuwa/OpenAI-compatible) for the
+//! higher-level action-suggestion and task pipeline; `OllamaClient` targets
+//! a plain local Ollama install (`/api/generate`, `/api/tags`).
+//!
+//! `provider` wraps both engines behind one `AiProvider` trait so a caller
+//! can pick a backend at runtime instead of hard-coding which one it talks
+//! to; `tokenizer` estimates prompt size so oversized clips get truncated
+//! before either backend sees them.
+
+pub mod provider;
+pub mod tokenizer;
+
+pub use provider::{select_provider, AiProvider};
+
+use crate::clipboard::monitor::ClipboardChange;
+use crate::clipboard::types::{BasicContentType, ClipboardEvent};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const DEFAULT_MODEL: &str = "llama3.2:1b";
+const DEFAULT_TIMEOUT_MS: u64 = 15_000;
+
+/// Event emitted to the frontend as streamed suggestion text arrives.
+const EVENT_SUGGESTION_CHUNK: &str = "ollama-suggestion-chunk";
+/// Event emitted once a change's suggestions are fully assembled.
+const EVENT_SUGGESTIONS_READY: &str = "ollama-suggestions-ready";
+
+#[derive(Debug, Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+}
+
+/// One line of Ollama's newline-delimited JSON stream from `/api/generate`.
+#[derive(Debug, Deserialize)]
+struct GenerateChunk {
+    response: String,
+    #[serde(default)]
+    done: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagModel {
+    name: String,
+}
+
+/// Payload sent alongside `EVENT_SUGGESTIONS_READY`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaSuggestions {
+    pub content_hash: String,
+    pub suggestions: Vec<String>,
+}
+
+pub struct OllamaClient {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    timeout: Duration,
+}
+
+impl OllamaClient {
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>, timeout_ms: u64) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.into(),
+            model: model.into(),
+            timeout: Duration::from_millis(timeout_ms),
+        }
+    }
+
+    /// Health-check probe: reuses `/api/tags` (the same endpoint the old
+    /// `test_ollama` binary used to list models) to confirm Ollama is up
+    /// and, optionally, that `self.model` is actually pulled.
+    pub async fn is_available(&self) -> bool {
+        self.list_models().await.is_ok()
+    }
+
+    pub async fn list_models(&self) -> Result<Vec<String>, String> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| format!("Unable to reach Ollama at {}: {}", url, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Ollama /api/tags returned {}", response.status()));
+        }
+
+        let tags: TagsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse /api/tags response: {}", e))?;
+
+        Ok(tags.models.into_iter().map(|m| m.name).collect())
+    }
+
+    /// Non-streaming suggestions for a clipboard event, split on `|` into a
+    /// flat list like the rest of the suggestion pipeline expects.
+    pub async fn suggest_actions(&self, event: &ClipboardEvent) -> Result<Vec<String>, String> {
+        let text = self.generate(&self.build_prompt(event), false, |_| {}).await?;
+        Ok(split_suggestions(&text))
+    }
+
+    /// Streaming variant: `on_chunk` is called with each incremental piece
+    /// of `response` text as Ollama produces it, so the caller can forward
+    /// partial output (e.g. to the frontend) before the full answer lands.
+    /// Returns the fully assembled suggestions once the stream completes.
+    pub async fn suggest_actions_stream(
+        &self,
+        event: &ClipboardEvent,
+        on_chunk: impl FnMut(&str),
+    ) -> Result<Vec<String>, String> {
+        let text = self.generate(&self.build_prompt(event), true, on_chunk).await?;
+        Ok(split_suggestions(&text))
+    }
+
+    /// Content-type-aware prompt: steers the model toward actions that
+    /// actually make sense for the kind of content copied, rather than
+    /// asking a single generic question for everything.
+    fn build_prompt(&self, event: &ClipboardEvent) -> String {
+        let content = &event.content;
+        let truncated = if content.chars().count() > 500 {
+            format!("{}...", content.chars().take(500).collect::<String>())
+        } else {
+            content.clone()
+        };
+
+        let hint = match event.content_type {
+            BasicContentType::Url => "opening it in a browser or previewing the page",
+            BasicContentType::Code => "explaining it or running/testing it",
+            BasicContentType::Address => "showing it on a map or starting navigation",
+            BasicContentType::Email => "composing a reply or saving the contact",
+            BasicContentType::Phone => "calling or saving the contact",
+            BasicContentType::Financial => "logging the expense or converting currency",
+            BasicContentType::DateTime => "adding it to the calendar",
+            BasicContentType::Image => "extracting text from it (OCR) or saving it",
+            BasicContentType::PlainText => "translating, summarizing, or searching it",
+        };
+
+        format!(
+            "The user just copied this content ({:?}): {}\n\nSuggest at most 3 short actions they'd likely want, such as {}. Reply with only the action names, separated by `|`, nothing else.",
+            event.content_type, truncated, hint
+        )
+    }
+
+    async fn generate(
+        &self,
+        prompt: &str,
+        stream: bool,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<String, String> {
+        let url = format!("{}/api/generate", self.base_url);
+        let request = GenerateRequest {
+            model: &self.model,
+            prompt,
+            stream,
+        };
+
+        info!("Sending Ollama /api/generate request (model `{}`, stream={})", self.model, stream);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("Ollama request failed: {}", e);
+                format!("Unable to reach Ollama at {}: {}", url, e)
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Ollama error {}: {}", status, body));
+        }
+
+        if !stream {
+            let chunk: GenerateChunk = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+            return Ok(chunk.response);
+        }
+
+        // Streaming mode: the body is newline-delimited JSON objects, one
+        // per generated token/fragment, each carrying a `response` piece to
+        // append and a `done` flag on the final line.
+        let mut assembled = String::new();
+        let mut buffer = String::new();
+        let mut byte_stream = response.bytes_stream();
+
+        use futures_util::StreamExt;
+        while let Some(next) = byte_stream.next().await {
+            let bytes = next.map_err(|e| format!("Error reading Ollama stream: {}", e))?;
+            buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer.drain(..=newline_pos);
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                let chunk: GenerateChunk = serde_json::from_str(&line)
+                    .map_err(|e| format!("Failed to parse Ollama stream chunk: {}", e))?;
+
+                if !chunk.response.is_empty() {
+                    on_chunk(&chunk.response);
+                    assembled.push_str(&chunk.response);
+                }
+
+                if chunk.done {
+                    debug!("Ollama stream finished");
+                }
+            }
+        }
+
+        Ok(assembled)
+    }
+}
+
+fn split_suggestions(text: &str) -> Vec<String> {
+    text.split('|')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Subscribe to the clipboard change stream and compute Ollama suggestions
+/// for every change automatically, streaming partial text to the frontend
+/// as `EVENT_SUGGESTION_CHUNK` and the final list as `EVENT_SUGGESTIONS_READY`.
+pub fn spawn_driver(
+    client: OllamaClient,
+    mut changes: broadcast::Receiver<ClipboardChange>,
+    app: AppHandle,
+) {
+    tokio::spawn(async move {
+        info!("Ollama suggestion driver started");
+        loop {
+            let change = match changes.recv().await {
+                Ok(change) => change,
+                Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    warn!("Ollama driver lagged, skipped {} events", count);
+                    continue;
+                }
+            };
+
+            let content_hash = change.event.content_hash.clone();
+            let app = app.clone();
+            let emit_app = app.clone();
+
+            let result = client
+                .suggest_actions_stream(&change.event, move |chunk| {
+                    let _ = emit_app.emit(EVENT_SUGGESTION_CHUNK, chunk.to_string());
+                })
+                .await;
+
+            match result {
+                Ok(suggestions) => {
+                    let _ = app.emit(
+                        EVENT_SUGGESTIONS_READY,
+                        OllamaSuggestions {
+                            content_hash,
+                            suggestions,
+                        },
+                    );
+                }
+                Err(e) => {
+                    warn!("Ollama suggestion generation failed: {}", e);
+                }
+            }
+        }
+        info!("Ollama suggestion driver stopped");
+    });
+}
@@ -0,0 +1,183 @@
+// src-tauri/src/ai/provider.rs
+//! Unifies the two concrete AI backends (`analyzer::ai_engine::AiEngine`,
+//! a hosted Chat-Completions-style API, and `OllamaClient`, a local Ollama
+//! install) behind one `AiProvider` trait, so callers can pick a backend
+//! at runtime instead of hard-coding which engine they talk to. Every
+//! provider is also handed content through `tokenizer::truncate_to_token_budget`
+//! first, so a huge clip can't blow the backend's context window.
+
+use super::tokenizer::{estimate_tokens, truncate_to_token_budget};
+use super::OllamaClient;
+use crate::analyzer::ai_engine::AiEngine;
+use crate::clipboard::types::{AiAnalysis, ClipboardError, ClipboardEvent, IntentPredictionRequest};
+
+use async_trait::async_trait;
+use std::env;
+use std::sync::Arc;
+
+/// Default context window (in estimated tokens) a provider's prompt is
+/// truncated to before being sent. Generous enough for most clips while
+/// still protecting small local models with short context windows.
+const DEFAULT_MAX_PROMPT_TOKENS: usize = 2000;
+
+#[async_trait]
+pub trait AiProvider: Send + Sync {
+    /// One-shot prediction: send `req` and wait for the complete result.
+    async fn predict_intents(&self, req: &IntentPredictionRequest) -> Result<AiAnalysis, ClipboardError>;
+
+    /// Same as `predict_intents`, but calls `on_token` with each piece of
+    /// generated text as it arrives, so a caller can stream partial output
+    /// (e.g. to the popup) instead of waiting for the full response.
+    async fn predict_intents_stream(
+        &self,
+        req: &IntentPredictionRequest,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<AiAnalysis, ClipboardError>;
+}
+
+fn budget_content(req: &IntentPredictionRequest, max_tokens: usize) -> String {
+    if estimate_tokens(&req.content) > max_tokens {
+        truncate_to_token_budget(&req.content, max_tokens)
+    } else {
+        req.content.clone()
+    }
+}
+
+fn analysis_from_predictions(predictions: Vec<crate::clipboard::types::AiActionSuggestion>) -> AiAnalysis {
+    let confidence = if !predictions.is_empty() {
+        predictions.iter().map(|p| p.confidence).sum::<f32>() / predictions.len() as f32
+    } else {
+        0.0
+    };
+
+    AiAnalysis {
+        intent_predictions: predictions,
+        summary: None,
+        confidence,
+        raw_response: None,
+    }
+}
+
+/// Hosted Chat-Completions-style backend. `AiEngine` doesn't expose a
+/// token-level streaming API (a single request gets a single response
+/// back), so the "stream" here is one chunk: the whole answer, delivered
+/// through `on_token` once it lands instead of being held back entirely.
+pub struct HostedApiProvider {
+    engine: AiEngine,
+    max_prompt_tokens: usize,
+}
+
+impl HostedApiProvider {
+    pub fn new(engine: AiEngine) -> Self {
+        Self {
+            engine,
+            max_prompt_tokens: DEFAULT_MAX_PROMPT_TOKENS,
+        }
+    }
+}
+
+#[async_trait]
+impl AiProvider for HostedApiProvider {
+    async fn predict_intents(&self, req: &IntentPredictionRequest) -> Result<AiAnalysis, ClipboardError> {
+        let content = budget_content(req, self.max_prompt_tokens);
+        let predictions = self
+            .engine
+            .predict_intent_with_context(&content, &req.content_type, req.context.as_ref())
+            .await
+            .map_err(ClipboardError::AiProcessingError)?;
+
+        Ok(analysis_from_predictions(predictions))
+    }
+
+    async fn predict_intents_stream(
+        &self,
+        req: &IntentPredictionRequest,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<AiAnalysis, ClipboardError> {
+        let analysis = self.predict_intents(req).await?;
+        if let Some(first) = analysis.intent_predictions.first() {
+            on_token(&first.label);
+        }
+        Ok(analysis)
+    }
+}
+
+/// Local Ollama-backed provider. Reuses `OllamaClient`'s genuine
+/// newline-delimited-JSON streaming, so `predict_intents_stream` forwards
+/// real incremental tokens rather than one lump chunk.
+pub struct LocalServerProvider {
+    client: OllamaClient,
+    max_prompt_tokens: usize,
+}
+
+impl LocalServerProvider {
+    pub fn new(client: OllamaClient) -> Self {
+        Self {
+            client,
+            max_prompt_tokens: DEFAULT_MAX_PROMPT_TOKENS,
+        }
+    }
+
+    fn budgeted_event(&self, req: &IntentPredictionRequest) -> ClipboardEvent {
+        let content = budget_content(req, self.max_prompt_tokens);
+        ClipboardEvent::new(content, req.content_type.clone(), None)
+    }
+}
+
+#[async_trait]
+impl AiProvider for LocalServerProvider {
+    async fn predict_intents(&self, req: &IntentPredictionRequest) -> Result<AiAnalysis, ClipboardError> {
+        let event = self.budgeted_event(req);
+        let suggestions = self
+            .client
+            .suggest_actions(&event)
+            .await
+            .map_err(ClipboardError::AiProcessingError)?;
+
+        Ok(AiAnalysis {
+            intent_predictions: Vec::new(),
+            summary: Some(suggestions.join(", ")),
+            confidence: 0.0,
+            raw_response: None,
+        })
+    }
+
+    async fn predict_intents_stream(
+        &self,
+        req: &IntentPredictionRequest,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<AiAnalysis, ClipboardError> {
+        let event = self.budgeted_event(req);
+        let suggestions = self
+            .client
+            .suggest_actions_stream(&event, |chunk| on_token(chunk))
+            .await
+            .map_err(ClipboardError::AiProcessingError)?;
+
+        Ok(AiAnalysis {
+            intent_predictions: Vec::new(),
+            summary: Some(suggestions.join(", ")),
+            confidence: 0.0,
+            raw_response: None,
+        })
+    }
+}
+
+/// Picks a provider at runtime from `CLIPMIND_AI_PROVIDER` (`"local"` or
+/// `"hosted"`), defaulting to the local Ollama install since that's what
+/// `start_clipboard_monitoring` already wires up automatically when it's
+/// reachable (see `spawn_driver`).
+pub fn select_provider() -> Arc<dyn AiProvider> {
+    match env::var("CLIPMIND_AI_PROVIDER").as_deref() {
+        Ok("hosted") => Arc::new(HostedApiProvider::new(AiEngine::new())),
+        _ => {
+            let base_url = env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| super::DEFAULT_BASE_URL.to_string());
+            let model = env::var("OLLAMA_MODEL").unwrap_or_else(|_| super::DEFAULT_MODEL.to_string());
+            Arc::new(LocalServerProvider::new(OllamaClient::new(
+                base_url,
+                model,
+                super::DEFAULT_TIMEOUT_MS,
+            )))
+        }
+    }
+}
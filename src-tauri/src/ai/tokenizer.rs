@@ -0,0 +1,92 @@
+// src-tauri/src/ai/tokenizer.rs
+//! Cheap token-count estimation for budgeting prompts before they're sent
+//! to an `AiProvider`. A real BPE tokenizer is model-specific and not
+//! worth vendoring just to decide whether to truncate; the ~4-characters-
+//! per-token rule of thumb (the same approximation OpenAI's own docs use
+//! for English text) is close enough to keep large clips from blowing a
+//! provider's context window.
+
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Rough token count for `text`.
+pub fn estimate_tokens(text: &str) -> usize {
+    let chars = text.chars().count();
+    ((chars + CHARS_PER_TOKEN - 1) / CHARS_PER_TOKEN).max(1)
+}
+
+/// Truncate `text` so `estimate_tokens` on the result fits within
+/// `max_tokens`, appending a marker so the caller (and the model) can see
+/// content was cut. Returns `text` unchanged if it already fits.
+pub fn truncate_to_token_budget(text: &str, max_tokens: usize) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+
+    let max_chars = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}... [truncated to fit context window]", truncated)
+}
+
+/// Same as `truncate_to_token_budget`, but keeps both the start and the end
+/// of `text` instead of a hard prefix cut - a hard cut loses the closing
+/// half of a long code block or document (a closing brace, a conclusion),
+/// which is often exactly the part a task like `explain_code` needs.
+pub fn truncate_preserving_ends(text: &str, max_tokens: usize) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+
+    const MARKER: &str = "\n... [truncated to fit context window] ...\n";
+    let budget_tokens = max_tokens.saturating_sub(estimate_tokens(MARKER)).max(2);
+    let head_tokens = budget_tokens - budget_tokens / 2;
+    let tail_tokens = budget_tokens / 2;
+
+    let chars: Vec<char> = text.chars().collect();
+    let head_chars = head_tokens.saturating_mul(CHARS_PER_TOKEN).min(chars.len());
+    let tail_chars = tail_tokens.saturating_mul(CHARS_PER_TOKEN).min(chars.len() - head_chars);
+
+    let head: String = chars[..head_chars].iter().collect();
+    let tail: String = chars[chars.len() - tail_chars..].iter().collect();
+
+    format!("{}{}{}", head, MARKER, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_unchanged() {
+        let text = "hello world";
+        assert_eq!(truncate_to_token_budget(text, 100), text);
+    }
+
+    #[test]
+    fn test_long_text_is_truncated() {
+        let text = "a".repeat(1000);
+        let truncated = truncate_to_token_budget(&text, 10);
+        assert!(estimate_tokens(&truncated) <= 10 + estimate_tokens("... [truncated to fit context window]"));
+        assert!(truncated.ends_with("[truncated to fit context window]"));
+    }
+
+    #[test]
+    fn test_estimate_tokens_is_at_least_one() {
+        assert_eq!(estimate_tokens(""), 1);
+    }
+
+    #[test]
+    fn test_preserving_ends_short_text_is_unchanged() {
+        let text = "hello world";
+        assert_eq!(truncate_preserving_ends(text, 100), text);
+    }
+
+    #[test]
+    fn test_preserving_ends_keeps_head_and_tail() {
+        let text = format!("{}{}", "head".repeat(100), "tail".repeat(100));
+        let truncated = truncate_preserving_ends(&text, 20);
+
+        assert!(truncated.starts_with("head"));
+        assert!(truncated.ends_with("tail"));
+        assert!(truncated.contains("truncated to fit context window"));
+    }
+}
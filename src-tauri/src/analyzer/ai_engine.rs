@@ -1,67 +1,238 @@
 // src-tauri/src/analyzer/ai_engine.rs
 use crate::clipboard::types::{
     AiAnalysis, AiActionSuggestion, AiActionType,
-    BasicContentType, RuleAnalysis
+    BasicContentType, PipelineResult, PipelineStep, RagAnswer, RuleAnalysis, UserContext,
 };
+use crate::analyzer::providers::{
+    Anthropic, ChatMessage, CompletionStream, LanguageModelProvider, OllamaNative, OpenAiCompatible,
+};
+use crate::history::ClipboardHistoryStore;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use url::Url;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
+use futures_util::{future, stream};
 use log::{info, warn};
 use std::env;
 use std::fs;                 // NEW: 讀檔
 use serde_yaml;              // NEW: 解析 YAML
 
-#[derive(Debug, Serialize)]
-struct ChatMessageReq {
-    role: String,
-    content: String,
-}
+/// Upper bound on `process_ai_pipeline`'s step count, so a chained request
+/// can't trigger an unbounded number of Genie/Chat API calls.
+const MAX_PIPELINE_STEPS: usize = 5;
 
-#[derive(Debug, Serialize)]
-struct ChatRequest {
-    model: String,
-    messages: Vec<ChatMessageReq>,
-}
+/// Word count above which `summarize`/`summarize_webpage` are routed
+/// through `summarize_large`'s map-reduce path instead of a single prompt,
+/// since a whole long page pasted into one prompt risks exceeding the
+/// model's context window.
+const SUMMARIZE_LARGE_THRESHOLD_WORDS: usize = 800;
+
+/// Chunk/overlap sizes for `summarize_large`'s "map" pass. We have no
+/// tokenizer here, so "words" (whitespace-split) stand in for tokens; the
+/// overlap keeps a sentence that straddles a chunk boundary from losing
+/// context on either side.
+const MAP_REDUCE_CHUNK_WORDS: usize = 800;
+const MAP_REDUCE_CHUNK_OVERLAP_WORDS: usize = 200;
+
+/// Default per-task prompt budget (tokens, see `ai::tokenizer`) for task
+/// types not called out in `task_token_budget`. `summarize`/
+/// `summarize_webpage` aren't budgeted here at all - long content for those
+/// already gets chunked by `summarize_large` before a prompt is built.
+const DEFAULT_TASK_TOKEN_BUDGET: usize = 4000;
+
+/// Default number of history entries `ask_history` retrieves before
+/// filtering by `RAG_MIN_SIMILARITY`.
+const RAG_TOP_K: usize = 5;
+
+/// Cosine-similarity floor below which a retrieved history entry is
+/// dropped instead of being stuffed into the prompt as a source - the
+/// default `HashEmbedder` isn't a real semantic model, so this is kept low
+/// rather than tuned for a proper embedding's score distribution.
+const RAG_MIN_SIMILARITY: f32 = 0.15;
+
+/// Upper bound on `run_agentic_task`'s tool-call loop, so a model that
+/// never settles on a final answer can't call tools forever.
+const MAX_AGENTIC_STEPS: usize = 5;
+
+/// Truncation limit for `fetch_url`'s tool result, so a huge page doesn't
+/// blow the next turn's prompt size.
+const FETCH_URL_MAX_CHARS: usize = 5000;
+
+/// Tools `run_agentic_task` exposes to the model: `(name, description)`.
+/// Kept as plain text rather than a formal JSON-schema struct since none of
+/// the `LanguageModelProvider` backends implement OpenAI-style structured
+/// function calling - the model is instead prompted to answer in the
+/// strict JSON shape `parse_agentic_step` expects, the same trick
+/// `build_intelligent_prompt`/`parse_ai_response` already use.
+const TOOL_CATALOG: &[(&str, &str)] = &[
+    ("translate", "Translate text into Traditional Chinese. Arguments: {\"content\": \"<text>\"}"),
+    ("summarize", "Summarize text concisely. Arguments: {\"content\": \"<text>\"}"),
+    ("explain_code", "Explain what a code snippet does. Arguments: {\"content\": \"<code>\"}"),
+    ("fetch_url", "Fetch the text content of a web page. Arguments: {\"url\": \"<url>\"}"),
+    ("search", "Search the user's clipboard history for entries related to a query. Arguments: {\"query\": \"<query>\"}"),
+];
 
+/// One step of `run_agentic_task`'s response: either a tool call or a final
+/// answer, matching the JSON shape described in `agentic_system_prompt`.
 #[derive(Debug, Deserialize)]
-struct ChatMessageResp {
-    role: String,
-    content: String,
+struct AgenticStep {
+    tool: Option<String>,
+    #[serde(default)]
+    arguments: HashMap<String, String>,
+    final_answer: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ChatChoice {
-    message: ChatMessageResp,
+/* ==================== YAML 設定 ==================== */
+/// One named backend a model can be routed to: `kind` picks which
+/// `LanguageModelProvider` impl to build (`"openai_compatible"`,
+/// `"ollama_native"`, `"anthropic"`); `url`/`api_key` override the
+/// top-level defaults for that provider specifically.
+#[derive(Debug, Deserialize, Clone)]
+struct ProviderConfig {
+    kind: String,
+    url: Option<String>,
+    api_key: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ChatResponse {
-    choices: Vec<ChatChoice>,
+/// A model entry in YAML is either a bare model-name string (routed to the
+/// `"default"` provider, preserving the original config shape) or a
+/// `{model, provider}` pair naming one of the `providers` map's entries.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+enum ModelEntry {
+    Simple(String),
+    Routed { model: String, provider: Option<String> },
+}
+
+impl ModelEntry {
+    fn model(&self) -> &str {
+        match self {
+            ModelEntry::Simple(model) => model,
+            ModelEntry::Routed { model, .. } => model,
+        }
+    }
+
+    fn provider(&self) -> Option<&str> {
+        match self {
+            ModelEntry::Simple(_) => None,
+            ModelEntry::Routed { provider, .. } => provider.as_deref(),
+        }
+    }
 }
 
-/* ==================== YAML 設定 ==================== */
 #[derive(Debug, Deserialize, Clone)]
 struct AiConfig {
     ollama_url: Option<String>,
     timeout_ms: Option<u64>,
     api_key: Option<String>,
-    models: Option<HashMap<String, String>>, // key: 行為名稱 / "default"
+    models: Option<HashMap<String, ModelEntry>>, // key: 行為名稱 / "default"
+    providers: Option<HashMap<String, ProviderConfig>>,
+    /// Worker-pool size for `process_ai_tasks_batch`; defaults to the
+    /// number of available CPUs when unset.
+    max_concurrency: Option<usize>,
+}
+
+/// One entry of a content type's configured action menu: the
+/// `action_id`/`label`/`icon`/`confidence`/`reason` that used to be baked
+/// into `action_catalog`'s match arms, plus the `keyword`/`number` the
+/// original numbered-menu prompts used before `parse_ai_response` moved to
+/// strict-JSON ids. `keyword`/`number` are kept as a fallback for replies
+/// that don't come back as the requested JSON (see `match_by_keywords`).
+#[derive(Debug, Deserialize, Clone)]
+struct PromptActionConfig {
+    keyword: Option<String>,
+    number: Option<u32>,
+    action_id: String,
+    label: String,
+    icon: String,
+    confidence: Option<f32>,
+    reason: Option<String>,
+}
+
+/// A content type's configured prompt: `prompt` is the task-specific
+/// template (with a `{content}` placeholder) that used to be inlined in
+/// `build_intelligent_prompt`'s match arms, and `actions` is its allow-list.
+#[derive(Debug, Deserialize, Clone)]
+struct ContentPromptConfig {
+    prompt: String,
+    #[serde(default)]
+    actions: Vec<PromptActionConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 struct AppConfig {
     ai: Option<AiConfig>,
+    /// Per-`BasicContentType` prompt/action-menu overrides, keyed by the
+    /// type's `Debug` name (e.g. `"PlainText"`, `"Code"`, `"Url"`). Content
+    /// types with no entry here keep using the built-in prompt/catalog.
+    prompts: Option<HashMap<String, ContentPromptConfig>>,
 }
 /* ================================================== */
 
+/// Strict shape the model is asked to answer in; see `build_intelligent_prompt`.
+#[derive(Debug, Deserialize)]
+struct RawActionsResponse {
+    actions: Vec<RawActionSuggestion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawActionSuggestion {
+    id: String,
+    confidence: Option<f32>,
+    reason: Option<String>,
+}
+
+/// Scans `text` for the first balanced `{...}` block and returns it, so a
+/// JSON object surrounded by prose ("Sure, here you go: {...}") can still
+/// be parsed. Brace/quote tracking ignores braces inside string literals.
+fn extract_balanced_json(text: &str) -> Option<String> {
+    let start = text.find('{')?;
+    let bytes = text.as_bytes();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escape = false;
+
+    for (i, &b) in bytes.iter().enumerate().skip(start) {
+        let c = b as char;
+        if in_string {
+            if escape {
+                escape = false;
+            } else if c == '\\' {
+                escape = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(text[start..=i].to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
 pub struct AiEngine {
-    client: Client,
-    ollama_url: String,
-    model_name: String,                 // 仍保留 default model，避免破壞既有呼叫
+    model_name: String,                                    // 仍保留 default model，避免破壞既有呼叫
     timeout_ms: u64,
-    api_key: Option<String>,
-    models: HashMap<String, String>,    // NEW: 行為 → 模型 對照表
+    models: HashMap<String, ModelEntry>,                    // 行為 → (模型, provider 名稱)
+    providers: HashMap<String, Arc<dyn LanguageModelProvider>>, // provider 名稱 → 實作
+    /// Worker-pool size for `process_ai_tasks_batch`.
+    max_concurrency: usize,
+    /// Per-content-type `prompts` overrides loaded from YAML; see
+    /// `ContentPromptConfig`. Empty when the config has no `prompts` section.
+    prompt_configs: HashMap<String, ContentPromptConfig>,
 }
 
 impl AiEngine {
@@ -83,6 +254,47 @@ impl AiEngine {
         }
     }
 
+    /// Builds one named `LanguageModelProvider` from its YAML config,
+    /// `None` if its `kind` is unrecognized or it's missing a field its
+    /// kind requires (e.g. Anthropic needs an `api_key`).
+    fn build_provider(
+        name: &str,
+        cfg: &ProviderConfig,
+        client: &Client,
+        fallback_url: &str,
+        fallback_api_key: Option<&str>,
+    ) -> Option<Arc<dyn LanguageModelProvider>> {
+        match cfg.kind.as_str() {
+            "openai_compatible" => Some(Arc::new(OpenAiCompatible::new(
+                client.clone(),
+                cfg.url.clone().unwrap_or_else(|| fallback_url.to_string()),
+                cfg.api_key.clone().or_else(|| fallback_api_key.map(str::to_string)),
+            ))),
+            "ollama_native" => Some(Arc::new(OllamaNative::new(
+                client.clone(),
+                cfg.url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
+            ))),
+            "anthropic" => {
+                let api_key = cfg.api_key.clone().or_else(|| fallback_api_key.map(str::to_string));
+                match api_key {
+                    Some(key) => Some(Arc::new(Anthropic::new(
+                        client.clone(),
+                        cfg.url.clone().unwrap_or_else(|| "https://api.anthropic.com".to_string()),
+                        key,
+                    ))),
+                    None => {
+                        warn!("Provider `{}` is kind `anthropic` but has no api_key configured, skipping", name);
+                        None
+                    }
+                }
+            }
+            other => {
+                warn!("Unknown provider kind `{}` for provider `{}`, skipping", other, name);
+                None
+            }
+        }
+    }
+
     pub fn new() -> Self {
         let default_url = "http://127.0.0.1/v1.0".to_string();
         let default_model = ".bot/Llama 3.2 3B @NPU".to_string();
@@ -112,42 +324,136 @@ impl AiEngine {
             .unwrap_or_else(HashMap::new);
 
         if !models.contains_key("default") {
-            models.insert("default".to_string(), default_model.clone());
+            models.insert("default".to_string(), ModelEntry::Simple(default_model.clone()));
         }
 
-        let model_name = models.get("default").cloned().unwrap_or(default_model);
+        let model_name = models.get("default").map(|e| e.model().to_string()).unwrap_or(default_model);
+
+        let client = Client::new();
+        let mut providers: HashMap<String, Arc<dyn LanguageModelProvider>> = HashMap::new();
+        providers.insert(
+            "default".to_string(),
+            Arc::new(OpenAiCompatible::new(client.clone(), ollama_url.clone(), api_key.clone())),
+        );
+
+        if let Some(provider_cfgs) = ai_cfg.as_ref().and_then(|a| a.providers.clone()) {
+            for (name, cfg) in provider_cfgs {
+                if let Some(provider) = Self::build_provider(&name, &cfg, &client, &ollama_url, api_key.as_deref()) {
+                    providers.insert(name, provider);
+                }
+            }
+        }
+
+        let max_concurrency = ai_cfg.as_ref()
+            .and_then(|a| a.max_concurrency)
+            .unwrap_or_else(Self::default_max_concurrency);
+
+        let prompt_configs = loaded.as_ref()
+            .and_then(|c| c.prompts.clone())
+            .unwrap_or_else(HashMap::new);
 
         Self {
-            client: Client::new(),
-            ollama_url,
             model_name,
             timeout_ms,
-            api_key,
             models,
+            providers,
+            max_concurrency,
+            prompt_configs,
         }
     }
 
-    fn pick_model(&self, task_type: &str) -> String {
-        self.models
-            .get(task_type)
-            .cloned()
-            .or_else(|| self.models.get("default").cloned())
-            .unwrap_or_else(|| self.model_name.clone())
+    /// `BasicContentType`'s `prompts` lookup key - its `Debug` name (e.g.
+    /// `"PlainText"`), since the enum isn't itself usable as a YAML map key.
+    fn content_type_key(basic_type: &BasicContentType) -> String {
+        format!("{:?}", basic_type)
+    }
+
+    /// Configured prompt/action-menu override for `basic_type`, if the
+    /// loaded YAML has a non-empty `prompts` entry for it.
+    fn prompt_config(&self, basic_type: &BasicContentType) -> Option<&ContentPromptConfig> {
+        self.prompt_configs
+            .get(&Self::content_type_key(basic_type))
+            .filter(|cfg| !cfg.actions.is_empty())
+    }
+
+    /// `prompt_config`'s actions as `action_catalog`-shaped
+    /// `(id, label, icon)` triples, ordered by the configured `number` (ids
+    /// with no `number` sort last), so a configured menu presents in the
+    /// same order an operator wrote it in YAML.
+    fn configured_catalog(cfg: &ContentPromptConfig) -> Vec<(&str, &str, &str)> {
+        let mut actions: Vec<&PromptActionConfig> = cfg.actions.iter().collect();
+        actions.sort_by_key(|a| a.number.unwrap_or(u32::MAX));
+        actions
+            .into_iter()
+            .map(|a| (a.action_id.as_str(), a.label.as_str(), a.icon.as_str()))
+            .collect()
+    }
+
+    /// Default `process_ai_tasks_batch` worker-pool size when
+    /// `AiConfig::max_concurrency` isn't set, mirroring the pool-sizing
+    /// approach `GenieWorkerPool` already uses.
+    fn default_max_concurrency() -> usize {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+    }
+
+    /// Resolves a task type to both the model string it should use and the
+    /// `LanguageModelProvider` that understands it, falling back to the
+    /// `"default"` entry/provider at each step.
+    fn pick_model(&self, task_type: &str) -> (Arc<dyn LanguageModelProvider>, String) {
+        let entry = self.models.get(task_type).or_else(|| self.models.get("default"));
+        let model = entry.map(|e| e.model().to_string()).unwrap_or_else(|| self.model_name.clone());
+        let provider_name = entry.and_then(|e| e.provider()).unwrap_or("default");
+
+        let provider = self.providers
+            .get(provider_name)
+            .or_else(|| self.providers.get("default"))
+            .expect("the `default` provider is always registered")
+            .clone();
+
+        (provider, model)
     }
 
     pub async fn predict_intent(
         &self,
         content: &str,
         basic_type: &BasicContentType,
+    ) -> Result<Vec<AiActionSuggestion>, String> {
+        self.predict_intent_with_context(content, basic_type, None).await
+    }
+
+    /// Same as `predict_intent`, but folds in `context` (recently-copied
+    /// clips the history store judged similar to this one) so the prompt
+    /// can nudge the model toward actions the user has reached for before.
+    pub async fn predict_intent_with_context(
+        &self,
+        content: &str,
+        basic_type: &BasicContentType,
+        context: Option<&UserContext>,
     ) -> Result<Vec<AiActionSuggestion>, String> {
         info!("Starting AI intent prediction, content type: {:?}", basic_type);
 
-        let prompt = self.build_intelligent_prompt(content, basic_type);
+        let budgeted_content =
+            crate::ai::tokenizer::truncate_preserving_ends(content, DEFAULT_TASK_TOKEN_BUDGET);
+        let mut prompt = self.build_intelligent_prompt(&budgeted_content, basic_type);
+        if let Some(context) = context {
+            if !context.recent_actions.is_empty() {
+                prompt.push_str(&format!(
+                    "\n\nFor reference, the user recently copied similar content:\n{}",
+                    context.recent_actions.join("\n")
+                ));
+            }
+            if let Some(secondary) = &context.secondary_candidate {
+                prompt.push_str(&format!(
+                    "\n\nNote: this content was ambiguous - it also scored close to {} during classification. Keep that alternate reading in mind.",
+                    secondary
+                ));
+            }
+        }
 
         // 意圖預測走 default 模型
-        let model = self.pick_model("default");
+        let (provider, model) = self.pick_model("default");
 
-        let response = match self.call_ollama(&prompt, 100000, &model).await {
+        let response = match self.call_provider(&prompt, 100000, &provider, &model).await {
             Ok(resp) => {
                 info!("Chat API response success: {}", &resp[..100.min(resp.len())]);
                 resp
@@ -164,16 +470,11 @@ impl AiEngine {
         Ok(suggestions)
     }
 
-    // Execute specific AI task (deep processing)
-    pub async fn process_ai_task(
-        &self,
-        content: &str,
-        task_type: &str,
-        _parameters: Option<HashMap<String, String>>,
-    ) -> Result<String, String> {
-        info!("Executing AI task: {}", task_type);
-
-        let prompt = match task_type {
+    /// Builds the task-specific prompt shared by `process_ai_task` and
+    /// `process_ai_task_stream`, so the two only differ in whether they
+    /// wait for the full completion or forward it incrementally.
+    fn build_task_prompt(&self, content: &str, task_type: &str) -> String {
+        match task_type {
             "translate" => {
                 format!(
                     "Translate the following content into Traditional Chinese, return only the translation:\n\n{}",
@@ -210,113 +511,595 @@ impl AiEngine {
                     content
                 )
             },
+            // `content` is only the `"[image WxH, N bytes]"` preview (see
+            // `ClipboardPayload::text_preview`) - this model is text-only,
+            // so it can't actually read the pixels. Until a vision-capable
+            // provider is wired in, this just asks it to reason about what
+            // a screenshot that size is likely to be, as a best-effort
+            // stand-in for real OCR.
+            "ocr_image" => {
+                format!(
+                    "A user copied a screenshot to their clipboard ({}). You can't see the pixels, so don't invent specific text; instead suggest in english, in no more than 100 characters, what they most likely want to do with it (e.g. extract text via OCR, save it, share it):\n\n{}",
+                    content, content
+                )
+            },
             _ => {
                 format!("Analyze the following content in in english:\n\n{}", content)
             }
-        };
+        }
+    }
+
+    // Execute specific AI task (deep processing)
+    pub async fn process_ai_task(
+        &self,
+        content: &str,
+        task_type: &str,
+        _parameters: Option<HashMap<String, String>>,
+    ) -> Result<String, String> {
+        info!("Executing AI task: {}", task_type);
 
-        // 這裡依任務類型挑選模型
-        let model = self.pick_model(task_type);
+        if Self::is_summarize_task(task_type) && Self::word_count(content) > SUMMARIZE_LARGE_THRESHOLD_WORDS {
+            return self.summarize_large(content, task_type).await;
+        }
+
+        let budgeted = crate::ai::tokenizer::truncate_preserving_ends(content, Self::task_token_budget(task_type));
+        let prompt = self.build_task_prompt(&budgeted, task_type);
 
-        let response = self.call_ollama(&prompt, 100000, &model).await?; // Allow more time for complex tasks
+        // 這裡依任務類型挑選模型與 provider
+        let (provider, model) = self.pick_model(task_type);
+
+        let response = self.call_provider(&prompt, 100000, &provider, &model).await?; // Allow more time for complex tasks
         Ok(response)
     }
 
-    // Test AI engine connection
-    pub async fn test_connection(&self) -> Result<bool, String> {
-        // Test chat/completions with a minimal request
-        let url = format!("{}/chat/completions", self.ollama_url);
-        let req = ChatRequest {
-            model: self.model_name.clone(),
-            messages: vec![ChatMessageReq {
-                role: "user".to_string(),
-                content: "hi".to_string(),
-            }],
-        };
+    /// Streaming variant of `process_ai_task`: same prompt/model/provider
+    /// selection, but hands back incremental chunks as the model generates
+    /// them instead of waiting for the whole response, so the Tauri
+    /// frontend can emit partial text for long summaries/translations.
+    pub async fn process_ai_task_stream(
+        &self,
+        content: &str,
+        task_type: &str,
+        _parameters: Option<HashMap<String, String>>,
+    ) -> Result<CompletionStream, String> {
+        info!("Executing AI task (streaming): {}", task_type);
+
+        if Self::is_summarize_task(task_type) && Self::word_count(content) > SUMMARIZE_LARGE_THRESHOLD_WORDS {
+            // The map-reduce passes already make several non-streaming
+            // calls internally; the caller still gets a stream, it just
+            // arrives as a single chunk once the reduce step finishes.
+            let summary = self.summarize_large(content, task_type).await?;
+            return Ok(Box::pin(stream::once(async move { Ok(summary) })));
+        }
+
+        let budgeted = crate::ai::tokenizer::truncate_preserving_ends(content, Self::task_token_budget(task_type));
+        let prompt = self.build_task_prompt(&budgeted, task_type);
+        let (provider, model) = self.pick_model(task_type);
+
+        self.call_provider_stream(&prompt, 100000, &provider, &model).await
+    }
+
+    /// Runs `process_ai_task` over every `(content, task_type)` pair
+    /// concurrently, bounded by `max_concurrency` workers, and returns one
+    /// `Result` per item in the same order as `items` - a failing item is
+    /// reported in its slot rather than aborting the rest of the batch.
+    pub async fn process_ai_tasks_batch(
+        &self,
+        items: Vec<(String, String)>,
+    ) -> Vec<Result<String, String>> {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.max_concurrency.max(1)));
+
+        let futures = items.into_iter().map(|(content, task_type)| {
+            let semaphore = semaphore.clone();
+            async move {
+                let _permit = semaphore.acquire().await.expect("semaphore is never closed");
+                self.process_ai_task(&content, &task_type, None).await
+            }
+        });
+
+        future::join_all(futures).await
+    }
 
-        let mut builder = self.client
-            .post(&url)
-            .json(&req)
-            .timeout(Duration::from_millis(self.timeout_ms));
+    fn is_summarize_task(task_type: &str) -> bool {
+        matches!(task_type, "summarize" | "summarize_webpage")
+    }
 
-        if let Some(key) = &self.api_key {
-            builder = builder.bearer_auth(key);
+    /// Prompt token budget for `task_type`, used to truncate `content` (via
+    /// `ai::tokenizer::truncate_preserving_ends`) before `build_task_prompt`
+    /// so a large clip can't blow the provider's context window. Code tasks
+    /// get more headroom than short-answer tasks like `extract_keywords`,
+    /// since the model needs to see the whole snippet to reason about it.
+    fn task_token_budget(task_type: &str) -> usize {
+        match task_type {
+            "explain_code" | "optimize_code" | "add_comments" => 6000,
+            "translate" => 4000,
+            "extract_keywords" | "ocr_image" => 2000,
+            _ => DEFAULT_TASK_TOKEN_BUDGET,
         }
+    }
 
-        match builder.send().await {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(e) => Err(format!("Unable to connect to Chat API: {}", e)),
+    fn word_count(content: &str) -> usize {
+        content.split_whitespace().count()
+    }
+
+    /// Splits `content` into overlapping word-count chunks, so the "map"
+    /// pass never has to hand the whole document to the model in one
+    /// prompt. Returns a single chunk unchanged if it's already short
+    /// enough.
+    fn split_into_overlapping_chunks(content: &str, chunk_words: usize, overlap_words: usize) -> Vec<String> {
+        let words: Vec<&str> = content.split_whitespace().collect();
+        if words.len() <= chunk_words {
+            return vec![content.to_string()];
         }
+
+        let step = chunk_words - overlap_words;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        loop {
+            let end = (start + chunk_words).min(words.len());
+            chunks.push(words[start..end].join(" "));
+            if end == words.len() {
+                break;
+            }
+            start += step;
+        }
+        chunks
     }
 
-    /// Call the Chat API
-    async fn call_ollama(&self, prompt: &str, timeout_ms: u64, model: &str) -> Result<String, String> {
-        let request = ChatRequest {
-            model: model.to_string(),
-            messages: vec![ChatMessageReq {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+    /// Map-reduce summarization for content too long to fit comfortably in
+    /// a single prompt: each chunk from `split_into_overlapping_chunks` is
+    /// summarized independently ("map"), then the chunk summaries are
+    /// combined into one final summary ("reduce"). Used in place of a
+    /// single `build_task_prompt` call whenever `process_ai_task`/
+    /// `process_ai_task_stream` see more than `SUMMARIZE_LARGE_THRESHOLD_WORDS`.
+    async fn summarize_large(&self, content: &str, task_type: &str) -> Result<String, String> {
+        let chunks = Self::split_into_overlapping_chunks(
+            content,
+            MAP_REDUCE_CHUNK_WORDS,
+            MAP_REDUCE_CHUNK_OVERLAP_WORDS,
+        );
+        info!("summarize_large: {} chunk(s) for map-reduce summarization", chunks.len());
+
+        let (provider, model) = self.pick_model(task_type);
+
+        let mut chunk_summaries = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let map_prompt = format!(
+                "Summarize this excerpt ({} of {}) concisely in english, preserving key facts and names:\n\n{}",
+                index + 1,
+                chunks.len(),
+                chunk
+            );
+            let summary = self
+                .call_provider(&map_prompt, 100000, &provider, &model)
+                .await
+                .map_err(|e| format!("Map step failed on chunk {}/{}: {}", index + 1, chunks.len(), e))?;
+            chunk_summaries.push(summary);
+        }
+
+        if chunk_summaries.len() == 1 {
+            return Ok(chunk_summaries.remove(0));
+        }
+
+        let reduce_prompt = format!(
+            "Combine the following excerpt summaries into one coherent summary in english (no more than 100 characters):\n\n{}",
+            chunk_summaries.join("\n\n")
+        );
+        self.call_provider(&reduce_prompt, 100000, &provider, &model)
+            .await
+            .map_err(|e| format!("Reduce step failed: {}", e))
+    }
+
+    /// Retrieval-augmented Q&A over clipboard history: finds the
+    /// `RAG_TOP_K` past entries in `history` most similar to `query`, drops
+    /// any below `RAG_MIN_SIMILARITY`, and asks the model to answer using
+    /// only those excerpts. Pass explicit `top_k`/`min_similarity` to
+    /// override the defaults.
+    pub async fn ask_history(
+        &self,
+        query: &str,
+        history: &ClipboardHistoryStore,
+        top_k: Option<usize>,
+        min_similarity: Option<f32>,
+    ) -> Result<RagAnswer, String> {
+        let top_k = top_k.unwrap_or(RAG_TOP_K);
+        let min_similarity = min_similarity.unwrap_or(RAG_MIN_SIMILARITY);
+
+        let sources: Vec<(String, String)> = history
+            .find_similar_scored(query, top_k)
+            .into_iter()
+            .filter(|m| m.score >= min_similarity)
+            .map(|m| (m.entry_id, m.event.content))
+            .collect();
+
+        self.answer_with_sources(query, &sources).await
+    }
+
+    /// Builds a "use only these sources" prompt out of `sources` (each an
+    /// `(entry_id, content)` pair), calls the model, and parses the
+    /// trailing `SOURCES:` line it's instructed to append back into a
+    /// `RagAnswer`, dropping any cited id the model didn't actually see.
+    async fn answer_with_sources(&self, query: &str, sources: &[(String, String)]) -> Result<RagAnswer, String> {
+        let prompt = Self::build_rag_prompt(query, sources);
+        let (provider, model) = self.pick_model("ask_history");
+        let response = self.call_provider(&prompt, 100000, &provider, &model).await?;
+        Ok(Self::parse_rag_response(&response, sources))
+    }
+
+    fn build_rag_prompt(query: &str, sources: &[(String, String)]) -> String {
+        if sources.is_empty() {
+            return format!(
+                "The user asked: \"{}\"\n\nNo clipboard history entries were relevant enough to answer this. \
+                Say plainly that you don't have enough information to answer instead of guessing, then on a \
+                final line write \"SOURCES:\" with nothing after it.",
+                query
+            );
+        }
+
+        let mut prompt = format!(
+            "Answer the user's question using ONLY the numbered sources below; do not use outside knowledge. \
+            If the sources don't contain enough information, say so instead of guessing.\n\nQuestion: {}\n\nSources:\n",
+            query
+        );
+        for (index, (entry_id, content)) in sources.iter().enumerate() {
+            prompt.push_str(&format!("[{}] (id={}) {}\n\n", index + 1, entry_id, content));
+        }
+        prompt.push_str(
+            "Answer the question, then on a final line write \"SOURCES:\" followed by a comma-separated \
+            list of the ids (not the [n] numbers) of the sources you actually used.",
+        );
+        prompt
+    }
+
+    /// Splits the model's reply on the last `SOURCES:` marker, keeping only
+    /// cited ids that correspond to a source we actually sent - a
+    /// hallucinated or mistyped id is dropped rather than surfaced to the
+    /// UI as a valid citation.
+    fn parse_rag_response(response: &str, sources: &[(String, String)]) -> RagAnswer {
+        let known_ids: Vec<&str> = sources.iter().map(|(id, _)| id.as_str()).collect();
+        let lower = response.to_lowercase();
+
+        let Some(pos) = lower.rfind("sources:") else {
+            return RagAnswer { answer: response.trim().to_string(), cited_entry_ids: Vec::new() };
         };
 
-        let url = format!("{}/chat/completions", self.ollama_url);
+        let answer = response[..pos].trim().to_string();
+        let raw_ids = &response[pos + "sources:".len()..];
+        let cited_entry_ids = raw_ids
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty() && known_ids.contains(s))
+            .map(|s| s.to_string())
+            .collect();
 
-        info!("Sending request to Chat API with model `{}`; timeout: {}ms", model, timeout_ms);
+        RagAnswer { answer, cited_entry_ids }
+    }
 
-        let mut builder = self.client
-            .post(&url)
-            .json(&request)
-            .timeout(Duration::from_millis(timeout_ms));
+    /// Multi-step tool-calling loop: lets the model fetch a URL, search
+    /// history, translate/summarize/explain a snippet, etc., using each
+    /// tool's result to decide its next move, until it returns a final
+    /// answer or `MAX_AGENTIC_STEPS` is reached. `history` is optional so
+    /// the `search` tool degrades to an error (fed back to the model, same
+    /// as any other tool failure) rather than this method requiring a
+    /// store that may have failed to open at startup.
+    pub async fn run_agentic_task(
+        &self,
+        content: &str,
+        basic_type: &BasicContentType,
+        history: Option<&ClipboardHistoryStore>,
+    ) -> Result<String, String> {
+        let (provider, model) = self.pick_model("agentic");
 
-        // Optional Bearer Token
-        if let Some(key) = &self.api_key {
-            builder = builder.bearer_auth(key);
+        let mut messages = vec![
+            ChatMessage { role: "system".to_string(), content: Self::agentic_system_prompt() },
+            ChatMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "Content type: {:?}\n\nContent:\n{}\n\nDecide whether you need a tool, or can answer directly.",
+                    basic_type, content
+                ),
+            },
+        ];
+
+        for step in 1..=MAX_AGENTIC_STEPS {
+            let response = provider.complete(&messages, &model, 100000).await?;
+
+            let Some(parsed) = extract_balanced_json(&response)
+                .and_then(|json| serde_json::from_str::<AgenticStep>(&json).ok())
+            else {
+                // Not the strict JSON we asked for; treat the whole reply
+                // as a plain-text final answer rather than erroring out.
+                return Ok(response.trim().to_string());
+            };
+
+            if let Some(answer) = parsed.final_answer {
+                return Ok(answer);
+            }
+
+            let Some(tool_name) = parsed.tool else {
+                return Ok(response.trim().to_string());
+            };
+
+            info!("run_agentic_task step {}: calling tool `{}`", step, tool_name);
+            let tool_result = self
+                .execute_tool(&tool_name, &parsed.arguments, history)
+                .await
+                .unwrap_or_else(|e| format!("Tool error: {}", e));
+
+            messages.push(ChatMessage { role: "assistant".to_string(), content: response });
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!("Tool `{}` result:\n{}", tool_name, tool_result),
+            });
+        }
+
+        Err(format!(
+            "Exceeded max agentic steps ({}) without a final answer",
+            MAX_AGENTIC_STEPS
+        ))
+    }
+
+    fn agentic_system_prompt() -> String {
+        let tools = TOOL_CATALOG
+            .iter()
+            .map(|(name, desc)| format!("- {}: {}", name, desc))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "You can use the following tools to help with the user's content:\n{}\n\n\
+            At each step, respond with ONLY a strict JSON object, no other text, in one of these forms:\n\
+            1. To call a tool: {{\"tool\":\"<tool name>\",\"arguments\":{{...}}}}\n\
+            2. To give your final answer: {{\"final_answer\":\"<your answer>\"}}\n\
+            Call as many tools as you need, one at a time, using each tool's result to decide the next step.",
+            tools
+        )
+    }
+
+    /// Runs one named tool from `TOOL_CATALOG` against `arguments`.
+    async fn execute_tool(
+        &self,
+        name: &str,
+        arguments: &HashMap<String, String>,
+        history: Option<&ClipboardHistoryStore>,
+    ) -> Result<String, String> {
+        match name {
+            "translate" | "summarize" | "explain_code" => {
+                let content = arguments
+                    .get("content")
+                    .ok_or_else(|| format!("`{}` tool requires a `content` argument", name))?;
+                self.process_ai_task(content, name, None).await
+            }
+            "fetch_url" => {
+                let url = arguments
+                    .get("url")
+                    .ok_or_else(|| "`fetch_url` tool requires a `url` argument".to_string())?;
+                Self::fetch_url(url).await
+            }
+            "search" => {
+                let query = arguments
+                    .get("query")
+                    .ok_or_else(|| "`search` tool requires a `query` argument".to_string())?;
+                let history = history.ok_or_else(|| "Clipboard history is unavailable".to_string())?;
+                let matches = history.find_similar(query, 3);
+                if matches.is_empty() {
+                    return Ok("No related clipboard history entries found.".to_string());
+                }
+                Ok(matches.into_iter().map(|e| e.content).collect::<Vec<_>>().join("\n---\n"))
+            }
+            other => Err(format!("Unknown tool `{}`", other)),
         }
+    }
 
-        let response = builder
+    /// Fetches `url` and returns its body as text, truncated to
+    /// `FETCH_URL_MAX_CHARS`. Uses a one-off client rather than a
+    /// provider's, since this is a plain HTTP GET, not a chat completion.
+    ///
+    /// `url` comes from the model's own tool call, which can itself be
+    /// steered by injected instructions in the content being analyzed, so
+    /// `validate_fetch_url` restricts it to http/https against a
+    /// non-private host before anything is sent.
+    async fn fetch_url(url: &str) -> Result<String, String> {
+        let url = Self::validate_fetch_url(url).await?;
+
+        let response = Client::new()
+            .get(url.clone())
+            .timeout(Duration::from_millis(10000))
             .send()
             .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    warn!("Chat API request timed out: {}ms", timeout_ms);
-                    format!("Request timed out ({}ms)", timeout_ms)
-                } else if e.is_connect() {
-                    warn!("Unable to connect to Chat API");
-                    "Unable to connect to Chat API".to_string()
-                } else {
-                    warn!("Chat API request failed: {}", e);
-                    format!("Request failed: {}", e)
-                }
-            })?;
+            .map_err(|e| format!("Failed to fetch `{}`: {}", url, e))?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            warn!("Chat API error {}: {}", status, body);
-            return Err(format!("Chat API error {}: {}", status, body));
+            return Err(format!("Fetching `{}` returned status {}", url, response.status()));
         }
 
-        let chat_response: ChatResponse = response
-            .json()
+        let text = response
+            .text()
             .await
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| format!("Failed to read response body for `{}`: {}", url, e))?;
 
-        let text = chat_response
-            .choices
-            .get(0)
-            .map(|c| c.message.content.clone())
-            .unwrap_or_default();
+        Ok(if text.chars().count() > FETCH_URL_MAX_CHARS {
+            format!("{}...", text.chars().take(FETCH_URL_MAX_CHARS).collect::<String>())
+        } else {
+            text
+        })
+    }
 
-        if text.is_empty() {
-            warn!("Chat API returned empty content");
-            return Err("Empty response".to_string());
+    /// `true` for loopback/private/link-local/unspecified addresses - the
+    /// shared predicate both the IP-literal and resolved-hostname checks in
+    /// `validate_fetch_url` apply.
+    fn is_blocked_ip(ip: std::net::IpAddr) -> bool {
+        match ip {
+            std::net::IpAddr::V4(v4) => {
+                v4.is_loopback() || v4.is_private() || v4.is_link_local() || v4.is_unspecified()
+            }
+            std::net::IpAddr::V6(v6) => {
+                v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xfe00) == 0xfc00
+            }
         }
+    }
 
-        info!("Chat API responded successfully: {}", &text[..50.min(text.len())]);
-        Ok(text)
+    /// Rejects anything but `http`/`https` and hosts that resolve to a
+    /// loopback, private, link-local, or otherwise non-public address (or
+    /// `localhost`) - a tool-call URL is model-generated from content that
+    /// can carry injected instructions, so without this an attacker-
+    /// registered hostname could make the app fetch internal admin/
+    /// metadata endpoints (SSRF). Unlike the lightweight host-suffix checks
+    /// `url_rules::matches_host` uses (which only pick a local action,
+    /// never make a network request), this resolves the host via
+    /// `tokio::net::lookup_host` and checks every address it returns,
+    /// since an IP-literal check alone lets any ordinary hostname through
+    /// unvalidated.
+    async fn validate_fetch_url(raw: &str) -> Result<Url, String> {
+        let url = Url::parse(raw).map_err(|e| format!("`{}` is not a valid URL: {}", raw, e))?;
+
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("`fetch_url` only supports http/https, got `{}`", url.scheme()));
+        }
+
+        let host = url.host_str().ok_or_else(|| format!("`{}` has no host", raw))?;
+        if host.eq_ignore_ascii_case("localhost") {
+            return Err(format!("`fetch_url` may not target `{}`", host));
+        }
+
+        if let Ok(ip) = host.parse::<std::net::IpAddr>() {
+            if Self::is_blocked_ip(ip) {
+                return Err(format!("`fetch_url` may not target private/internal address `{}`", ip));
+            }
+            return Ok(url);
+        }
+
+        let port = url.port_or_known_default().unwrap_or(80);
+        let resolved = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| format!("Failed to resolve host `{}`: {}", host, e))?;
+
+        for addr in resolved {
+            if Self::is_blocked_ip(addr.ip()) {
+                return Err(format!(
+                    "`fetch_url` may not target `{}`, which resolves to private/internal address `{}`",
+                    host, addr.ip()
+                ));
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Runs `steps` in sequence, feeding each step's string output in as the
+    /// next step's content (e.g. "explain_code" then "translate", so a
+    /// foreign-language snippet can be explained and translated in one
+    /// call instead of two manual round trips). Capped at
+    /// `MAX_PIPELINE_STEPS` and short-circuits with a descriptive error on
+    /// the first failing step.
+    pub async fn process_ai_pipeline(
+        &self,
+        content: &str,
+        steps: Vec<PipelineStep>,
+    ) -> Result<PipelineResult, String> {
+        if steps.is_empty() {
+            return Err("Pipeline must have at least one step".to_string());
+        }
+        if steps.len() > MAX_PIPELINE_STEPS {
+            return Err(format!(
+                "Pipeline has {} steps, exceeding the max of {}",
+                steps.len(),
+                MAX_PIPELINE_STEPS
+            ));
+        }
+
+        let mut current = content.to_string();
+        let mut step_outputs = Vec::with_capacity(steps.len());
+
+        for (index, step) in steps.into_iter().enumerate() {
+            let output = self
+                .process_ai_task(&current, &step.task_type, step.parameters)
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Pipeline failed at step {} ({}): {}",
+                        index + 1,
+                        step.task_type,
+                        e
+                    )
+                })?;
+            step_outputs.push(output.clone());
+            current = output;
+        }
+
+        Ok(PipelineResult {
+            final_output: current,
+            step_outputs,
+        })
     }
 
-    /// Build intelligent prompt
+    // Test AI engine connection
+    pub async fn test_connection(&self) -> Result<bool, String> {
+        let provider = self.providers.get("default").expect("the `default` provider is always registered");
+        let messages = vec![ChatMessage { role: "user".to_string(), content: "hi".to_string() }];
+
+        match provider.complete(&messages, &self.model_name, self.timeout_ms).await {
+            Ok(_) => Ok(true),
+            Err(e) => Err(format!("Unable to connect to Chat API: {}", e)),
+        }
+    }
+
+    /// Wraps a single-prompt request as one user `ChatMessage` and hands it
+    /// to `provider`, collecting the full completion before returning.
+    async fn call_provider(
+        &self,
+        prompt: &str,
+        timeout_ms: u64,
+        provider: &Arc<dyn LanguageModelProvider>,
+        model: &str,
+    ) -> Result<String, String> {
+        let messages = vec![ChatMessage { role: "user".to_string(), content: prompt.to_string() }];
+        provider.complete(&messages, model, timeout_ms).await
+    }
+
+    /// Streaming counterpart to `call_provider`, so a long summary/
+    /// translation can start showing up in the popup before `provider` is
+    /// done generating it.
+    async fn call_provider_stream(
+        &self,
+        prompt: &str,
+        timeout_ms: u64,
+        provider: &Arc<dyn LanguageModelProvider>,
+        model: &str,
+    ) -> Result<CompletionStream, String> {
+        let messages = vec![ChatMessage { role: "user".to_string(), content: prompt.to_string() }];
+        provider.complete_stream(&messages, model, timeout_ms).await
+    }
+
+    /// Allow-listed `(id, label, icon)` triples the model is permitted to
+    /// pick from for a given content type. `parse_ai_response` rejects any
+    /// `id` not in this list, so a hallucinated or malformed id can't turn
+    /// into a bogus action in the popup.
+    fn action_catalog(basic_type: &BasicContentType) -> &'static [(&'static str, &'static str, &'static str)] {
+        match basic_type {
+            BasicContentType::Url => &[
+                ("ai_summarize_webpage", "AI Summarize Webpage", "📖"),
+                ("ai_translate_webpage", "AI Translate", "🌐"),
+            ],
+            BasicContentType::Code => &[
+                ("ai_explain_code", "AI Explain Code", "💡"),
+                ("ai_optimize_code", "AI Optimize Code", "⚡"),
+                ("ai_add_comments", "AI Add Comments", "📝"),
+            ],
+            BasicContentType::PlainText => &[
+                ("ai_translate", "AI Translate", "📋"),
+                ("ai_summarize", "AI Summarize", "📋"),
+                ("ai_extract_keywords", "AI Extract Keywords", "🔑"),
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Build intelligent prompt. Asks the model to answer with a strict
+    /// JSON object naming ids from `action_catalog` (or its `prompts`-config
+    /// override) instead of free text, since matching literal numbers/words
+    /// against prose ("I suggest options 1 and 3 because...") broke the
+    /// moment the model got chatty.
     fn build_intelligent_prompt(&self, content: &str, basic_type: &BasicContentType) -> String {
         let truncated_content = if content.len() > 300 {
             format!("{}...", &content[..300])
@@ -324,149 +1107,163 @@ impl AiEngine {
             content.to_string()
         };
 
-        match basic_type {
-            BasicContentType::Url => {
-                format!(
-                    "Analyze the URL: {}\n\nChoose 2-3 of the most appropriate next actions from the following:\n1. Open in browser\n2. Summarize webpage\n3. Search related info\n4. Save bookmark\n5. Translate webpage\n\nAnswer format: Action2,Action3,Action5,, DO NOT answer anything esle, DO NOT explain",
-                    truncated_content
-                )
-            },
-            BasicContentType::Code => {
-                format!(
-                    "Analyze the code: {}\n\nChoose 2-3 of the most appropriate next actions from the following:\n1. Explain code functionality\n2. Optimization suggestions\n3. Find errors\n4. Format code\n5. Search documentation\n6. Add comments\n\nAnswer format: Action1,Action2,Action5, DO NOT answer anything esle, DO NOT explain",
-                    truncated_content
-                )
-            },
+        if let Some(cfg) = self.prompt_config(basic_type) {
+            let ids: Vec<&str> = Self::configured_catalog(cfg).iter().map(|(id, _, _)| *id).collect();
+            let description = cfg.prompt.replace("{content}", &truncated_content);
+            return Self::render_action_prompt(&description, &ids);
+        }
+
+        let catalog = Self::action_catalog(basic_type);
+        if catalog.is_empty() {
+            return format!(
+                "Analyze content: {}\n\nRespond with ONLY this exact strict JSON object, no other text: {{\"actions\":[]}}",
+                truncated_content
+            );
+        }
+
+        let ids: Vec<&str> = catalog.iter().map(|(id, _, _)| *id).collect();
+        let description = match basic_type {
             BasicContentType::PlainText => {
                 let language = if self.is_english(&truncated_content) { "English" } else { "Traditional Chinese" };
-                format!(
-                    "Analyze {} text: {}\n\nChoose 2-3 of the most appropriate actions from the following:\n1. Translate\n2. Summarize\n3. Extract keywords\n4. Sentiment analysis\n5. Search related\n6. Rewrite/improve\n\nAnswer format: Action1,Action2,Action3, DO NOT answer anything esle, DO NOT explain",
-                    language, truncated_content
-                )
-            },
-            _ => {
-                format!(
-                    "Analyze content: {}\n\nSuggested actions:\n1. Search related info\n2. Save as note\n\nAnswer format: Action1,Action2, DO NOT answer anything esle, DO NOT explain",
-                    truncated_content
-                )
+                format!("Analyze this {} text: {}", language, truncated_content)
             }
-        }
+            BasicContentType::Url => format!("Analyze this URL: {}", truncated_content),
+            BasicContentType::Code => format!("Analyze this code: {}", truncated_content),
+            _ => format!("Analyze this content: {}", truncated_content),
+        };
+
+        Self::render_action_prompt(&description, &ids)
     }
 
-    // Parse AI response
-    fn parse_ai_response(&self, response: &str, basic_type: &BasicContentType) -> Vec<AiActionSuggestion> {
-        let mut suggestions = Vec::new();
-        let response_lower = response.to_lowercase();
+    /// Shared suffix appended to a content-type's description (built-in or
+    /// `prompts`-config) asking for the strict-JSON action response -
+    /// factored out so a configured `prompt` template only has to supply
+    /// the content-specific lead-in, not the JSON-shape boilerplate.
+    fn render_action_prompt(description: &str, ids: &[&str]) -> String {
+        format!(
+            "{}\n\nChoose 1-3 of the most relevant actions from this allow-list of ids: {}.\n\
+            Respond with ONLY a strict JSON object of the form \
+            {{\"actions\":[{{\"id\":\"<one of the allowed ids>\",\"confidence\":0.0-1.0,\"reason\":\"short reason\"}}]}}. \
+            Do not include any text outside the JSON object.",
+            description, ids.join(", ")
+        )
+    }
 
+    // Parse AI response: scan for the first balanced `{...}` block and
+    // parse it as JSON, validating each `id` against `action_catalog` (or
+    // its `prompts`-config override) for this content type. Falls back to
+    // keyword matching, then to fixed suggestions, if no JSON with at least
+    // one known action id is found.
+    fn parse_ai_response(&self, response: &str, basic_type: &BasicContentType) -> Vec<AiActionSuggestion> {
         info!("Parsing AI response: {}", &response[..100.min(response.len())]);
 
-        match basic_type {
-            BasicContentType::Url => {
-                if response_lower.contains("summarize") || response_lower.contains("abstract") || response_lower.contains("2") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_summarize_webpage".to_string(),
-                        label: "AI Summarize Webpage".to_string(),
-                        icon: "📖".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.9,
-                        reason: Some("AI suggested summarizing webpage".to_string()),
-                        parameters: None,
-                    });
-                }
-                if response_lower.contains("translate") || response_lower.contains("5") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_translate_webpage".to_string(),
-                        label: "AI Translate".to_string(),
-                        icon: "🌐".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.8,
-                        reason: Some("AI suggested translating webpage".to_string()),
-                        parameters: None,
-                    });
-                }
-            },
-            BasicContentType::Code => {
-                if response_lower.contains("explain") || response_lower.contains("1") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_explain_code".to_string(),
-                        label: "AI Explain Code".to_string(),
-                        icon: "💡".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.95,
-                        reason: Some("AI suggested explaining code functionality".to_string()),
-                        parameters: None,
-                    });
-                }
-                if response_lower.contains("optimize") || response_lower.contains("2") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_optimize_code".to_string(),
-                        label: "AI Optimize Code".to_string(),
-                        icon: "⚡".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.8,
-                        reason: Some("AI suggested code optimization".to_string()),
-                        parameters: None,
-                    });
-                }
-                if response_lower.contains("comment") || response_lower.contains("6") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_add_comments".to_string(),
-                        label: "AI Add Comments".to_string(),
-                        icon: "📝".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.7,
-                        reason: Some("AI suggested adding code comments".to_string()),
-                        parameters: None,
-                    });
-                }
-            },
-            BasicContentType::PlainText => {
-                if response_lower.contains("translate") || response_lower.contains("1") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_translate".to_string(),
-                        label: "AI Translate".to_string(),
-                        icon: "📋".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.82,
-                        reason: Some("AI suggested translating this text".to_string()),
-                        parameters: None,
-                    });
-                }
-                if response_lower.contains("summarize") || response_lower.contains("abstract") || response_lower.contains("2") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_summarize".to_string(),
-                        label: "AI Summarize".to_string(),
-                        icon: "📋".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.8,
-                        reason: Some("AI suggested generating a summary".to_string()),
-                        parameters: None,
-                    });
-                }
-                if response_lower.contains("keyword") || response_lower.contains("3") {
-                    suggestions.push(AiActionSuggestion {
-                        action_id: "ai_extract_keywords".to_string(),
-                        label: "AI Extract Keywords".to_string(),
-                        icon: "🔑".to_string(),
-                        action_type: AiActionType::AiProcessing,
-                        confidence: 0.7,
-                        reason: Some("AI suggested extracting key information".to_string()),
-                        parameters: None,
-                    });
-                }
-            },
-            _ => {}
+        let configured = self.prompt_config(basic_type);
+        let owned_catalog;
+        let catalog: &[(&str, &str, &str)] = match configured {
+            Some(cfg) => {
+                owned_catalog = Self::configured_catalog(cfg);
+                &owned_catalog
+            }
+            None => Self::action_catalog(basic_type),
+        };
+
+        let parsed = extract_balanced_json(response).and_then(|json| {
+            serde_json::from_str::<RawActionsResponse>(&json).ok()
+        });
+
+        if let Some(raw) = parsed {
+            let suggestions: Vec<AiActionSuggestion> = raw
+                .actions
+                .into_iter()
+                .filter_map(|action| {
+                    catalog
+                        .iter()
+                        .find(|(id, _, _)| *id == action.id)
+                        .map(|(id, label, icon)| AiActionSuggestion {
+                            action_id: id.to_string(),
+                            label: label.to_string(),
+                            icon: icon.to_string(),
+                            action_type: AiActionType::AiProcessing,
+                            confidence: action.confidence.unwrap_or(0.75).clamp(0.0, 1.0),
+                            reason: action.reason,
+                            parameters: None,
+                        })
+                })
+                .collect();
+
+            if !suggestions.is_empty() {
+                return suggestions;
+            }
+            warn!("AI JSON response had no actions matching the allow-list for {:?}", basic_type);
+        } else {
+            warn!("AI response contained no parseable JSON object, falling back");
         }
 
-        if suggestions.is_empty() {
-            suggestions = self.get_fallback_suggestions(basic_type);
+        if let Some(cfg) = configured {
+            let matched = Self::match_by_keywords(response, &cfg.actions);
+            if !matched.is_empty() {
+                return matched;
+            }
         }
 
-        suggestions
+        self.get_fallback_suggestions(basic_type)
+    }
+
+    /// Last-resort parse for a `prompts`-configured content type when the
+    /// model didn't reply with the requested JSON at all: scans `response`
+    /// for each action's configured `keyword` (case-insensitive substring
+    /// match) and returns a suggestion per hit, in `number` order. Mirrors
+    /// the numbered-menu matching this config format replaces
+    /// `action_catalog`-style (built-in, no keywords) content types skip
+    /// this step entirely.
+    fn match_by_keywords(response: &str, actions: &[PromptActionConfig]) -> Vec<AiActionSuggestion> {
+        let lower = response.to_lowercase();
+        let mut matched: Vec<&PromptActionConfig> = actions
+            .iter()
+            .filter(|a| {
+                a.keyword
+                    .as_deref()
+                    .map(|k| !k.is_empty() && lower.contains(&k.to_lowercase()))
+                    .unwrap_or(false)
+            })
+            .collect();
+        matched.sort_by_key(|a| a.number.unwrap_or(u32::MAX));
+
+        matched
+            .into_iter()
+            .map(|a| AiActionSuggestion {
+                action_id: a.action_id.clone(),
+                label: a.label.clone(),
+                icon: a.icon.clone(),
+                action_type: AiActionType::AiProcessing,
+                confidence: a.confidence.unwrap_or(0.6).clamp(0.0, 1.0),
+                reason: a.reason.clone(),
+                parameters: None,
+            })
+            .collect()
     }
 
-    /// Fallback suggestions
+    /// Fallback suggestions: the `prompts`-configured actions for this
+    /// content type verbatim (every configured action, in `number` order),
+    /// if any are configured, else the built-in fixed suggestions below.
     fn get_fallback_suggestions(&self, basic_type: &BasicContentType) -> Vec<AiActionSuggestion> {
+        if let Some(cfg) = self.prompt_config(basic_type) {
+            let mut actions: Vec<&PromptActionConfig> = cfg.actions.iter().collect();
+            actions.sort_by_key(|a| a.number.unwrap_or(u32::MAX));
+            return actions
+                .into_iter()
+                .map(|a| AiActionSuggestion {
+                    action_id: a.action_id.clone(),
+                    label: a.label.clone(),
+                    icon: a.icon.clone(),
+                    action_type: AiActionType::AiProcessing,
+                    confidence: a.confidence.unwrap_or(0.7).clamp(0.0, 1.0),
+                    reason: a.reason.clone().or_else(|| Some(format!("Fallback suggestion: {}", a.label))),
+                    parameters: None,
+                })
+                .collect();
+        }
+
         match basic_type {
             BasicContentType::Url => vec![
                 AiActionSuggestion {
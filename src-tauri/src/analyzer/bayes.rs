@@ -0,0 +1,258 @@
+// src-tauri/src/analyzer/bayes.rs
+//! Trainable local text classifier for `PlainText`/ambiguous content.
+//!
+//! `RuleEngine` otherwise has nothing better to offer `PlainText` than
+//! "ask the AI" (see `rule_engine::analyze`), which means every plain note
+//! round-trips to the network even for content the user has told us about
+//! before (e.g. "this is a shopping list" by repeatedly accepting the same
+//! suggestion on similar clips). `BayesClassifier` learns those categories
+//! locally instead: `train(text, category)` records one example, and
+//! `classify(text)` returns the best-matching category and a confidence
+//! score, so `RuleEngine` can skip the AI call once it has learned enough.
+//!
+//! Tokenization is OSB (orthogonal sparse bigrams, as used by SpamBayes/
+//! CRM114): for a sliding window of up to 5 tokens, the first token is
+//! paired with each later token in the window, tagged with their gap
+//! distance. This captures short-range word order/context ("order" near
+//! "confirmed" vs. "order" near "pizza") without the combinatorial blow-up
+//! of full n-grams. Each feature is hashed into a pair of 32-bit values so
+//! the per-feature count table stays a fixed-key `HashMap` instead of
+//! growing a `String` key per feature.
+//!
+//! Classification combines per-feature probabilities with Robinson/Fisher's
+//! method (as used by SpamBayes): each feature's "this category vs. every
+//! other category" probability is smoothed toward 0.5 when it's only been
+//! seen a handful of times, then combined into a single 0.0-1.0 score.
+//! Scores near 0.5 mean the classifier has no real opinion (e.g. an unseen
+//! category, or too few training examples), so `RuleEngine` should still
+//! fall back to the AI in that case.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Sliding-window size for OSB feature generation: the first token in the
+/// window is paired with each of the next `OSB_WINDOW - 1` tokens.
+const OSB_WINDOW: usize = 5;
+
+/// Robinson smoothing strength (`s` in `p' = (0.5*s + n*p) / (s+n)`): how
+/// many "virtual" neutral observations a never/rarely-seen feature starts
+/// with, pulling its probability toward 0.5 until real counts accumulate.
+const SMOOTHING_STRENGTH: f32 = 1.0;
+
+/// A Robinson/Fisher combined score this close to 0.5 is treated as "no
+/// real signal" by callers (see `classify`'s doc comment) rather than a
+/// genuine classification.
+pub const INCONCLUSIVE_MARGIN: f32 = 0.1;
+
+/// One OSB feature's hashed identity: two independent 32-bit hashes of the
+/// same token pair, used as the feature-count table's key. Two hashes
+/// (rather than one `u64`) mirror the classic CRM114/SpamBayes table shape
+/// and halve the odds of two distinct features colliding.
+type FeatureKey = (u32, u32);
+
+/// Local, trainable Naive-Bayes-style text classifier. Counts are kept
+/// per-category in memory only (see module docs); nothing here persists
+/// across restarts - callers that want that can serialize `feature_counts`
+/// themselves, which isn't needed yet.
+#[derive(Debug, Default)]
+pub struct BayesClassifier {
+    /// feature -> category -> number of training examples of that category
+    /// containing that feature.
+    feature_counts: HashMap<FeatureKey, HashMap<String, u32>>,
+}
+
+impl BayesClassifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one training example: every OSB feature in `text` has its
+    /// count under `category` incremented by one.
+    pub fn train(&mut self, text: &str, category: &str) {
+        for feature in Self::osb_features(text) {
+            *self
+                .feature_counts
+                .entry(Self::hash_feature(&feature))
+                .or_default()
+                .entry(category.to_string())
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Classifies `text` against every category seen so far, returning the
+    /// best-scoring `(category, score)` pair, or `None` if nothing has ever
+    /// been trained. A `score` within `INCONCLUSIVE_MARGIN` of 0.5 means the
+    /// classifier doesn't actually have an opinion - callers should treat
+    /// that the same as `None` and fall back to a heavier-weight method
+    /// (e.g. the AI engine) instead of trusting the label.
+    pub fn classify(&self, text: &str) -> Option<(String, f32)> {
+        let categories = self.known_categories();
+        if categories.is_empty() {
+            return None;
+        }
+
+        let features: Vec<FeatureKey> = Self::osb_features(text)
+            .iter()
+            .map(|f| Self::hash_feature(f))
+            .collect();
+
+        categories
+            .into_iter()
+            .map(|category| {
+                let score = self.score_category(&features, &category);
+                (category, score)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    fn known_categories(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        for counts in self.feature_counts.values() {
+            for category in counts.keys() {
+                seen.insert(category.clone());
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    /// Robinson/Fisher combination of every feature's smoothed "is this
+    /// `category`" probability into a single 0.0-1.0 score; see module docs.
+    fn score_category(&self, features: &[FeatureKey], category: &str) -> f32 {
+        let mut sum_ln = 0.0f32;
+        let mut scored_features = 0usize;
+
+        for key in features {
+            let Some(counts) = self.feature_counts.get(key) else {
+                continue;
+            };
+
+            let ws = *counts.get(category).unwrap_or(&0) as f32;
+            let wh: f32 = counts
+                .iter()
+                .filter(|(other, _)| other.as_str() != category)
+                .map(|(_, count)| *count as f32)
+                .sum();
+
+            let n = ws + wh;
+            if n <= 0.0 {
+                continue;
+            }
+
+            let p = ws / n;
+            let smoothed = ((0.5 * SMOOTHING_STRENGTH) + n * p) / (SMOOTHING_STRENGTH + n);
+            // Clamp away from the exact bounds so `ln` never sees 0 or infinity.
+            let smoothed = smoothed.clamp(1e-6, 1.0 - 1e-6);
+
+            sum_ln += ((1.0 - smoothed) / smoothed).ln();
+            scored_features += 1;
+        }
+
+        if scored_features == 0 {
+            return 0.5;
+        }
+
+        1.0 / (1.0 + sum_ln.exp())
+    }
+
+    /// Lowercased, alphanumeric-run tokenization - simple whitespace/
+    /// punctuation splitting is enough here since OSB features (not raw
+    /// tokens) are what carries the context signal.
+    fn tokenize(text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.to_string())
+            .collect()
+    }
+
+    /// Orthogonal sparse bigrams: for each token, paired with every later
+    /// token within an `OSB_WINDOW`-token lookahead, tagged with their gap
+    /// distance so "a b" (gap 1) and "a _ b" (gap 2) stay distinct features.
+    /// Falls back to plain unigrams for inputs too short to form any pair,
+    /// so a one- or two-word clip still yields at least one feature.
+    fn osb_features(text: &str) -> Vec<String> {
+        let tokens = Self::tokenize(text);
+
+        let mut features = Vec::new();
+        for i in 0..tokens.len() {
+            let window_end = (i + OSB_WINDOW).min(tokens.len());
+            for j in (i + 1)..window_end {
+                features.push(format!("{}:{}:{}", tokens[i], j - i, tokens[j]));
+            }
+        }
+
+        if features.is_empty() {
+            features.extend(tokens);
+        }
+        features
+    }
+
+    /// Hashes `feature` into two independent 32-bit values using two
+    /// differently-salted `DefaultHasher`s, so the feature-count table's
+    /// key is a fixed-size `(u32, u32)` instead of the `String` itself.
+    fn hash_feature(feature: &str) -> FeatureKey {
+        let mut first = DefaultHasher::new();
+        feature.hash(&mut first);
+        let h1 = first.finish() as u32;
+
+        let mut second = DefaultHasher::new();
+        0x9e3779b97f4a7c15u64.hash(&mut second); // salt, so h2 != h1
+        feature.hash(&mut second);
+        let h2 = second.finish() as u32;
+
+        (h1, h2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_after_training() {
+        let mut bayes = BayesClassifier::new();
+        for _ in 0..5 {
+            bayes.train("buy milk eggs and bread at the store", "shopping");
+            bayes.train("standup meeting notes action items for next sprint", "meeting");
+        }
+
+        let (category, score) = bayes.classify("pick up milk and bread from the store").unwrap();
+        assert_eq!(category, "shopping");
+        assert!(score > 0.5 + INCONCLUSIVE_MARGIN, "score was {}", score);
+    }
+
+    #[test]
+    fn untrained_classifier_returns_none() {
+        let bayes = BayesClassifier::new();
+        assert!(bayes.classify("anything at all").is_none());
+    }
+
+    #[test]
+    fn unrelated_text_is_inconclusive() {
+        let mut bayes = BayesClassifier::new();
+        bayes.train("buy milk eggs and bread at the store", "shopping");
+        bayes.train("standup meeting notes action items for next sprint", "meeting");
+
+        let (_, score) = bayes.classify("quantum entanglement violates bell inequalities").unwrap();
+        assert!(
+            (score - 0.5).abs() < INCONCLUSIVE_MARGIN,
+            "expected an inconclusive score, got {}",
+            score
+        );
+    }
+
+    #[test]
+    fn osb_features_include_gap_tagged_pairs() {
+        let features = BayesClassifier::osb_features("a b c");
+        assert!(features.contains(&"a:1:b".to_string()));
+        assert!(features.contains(&"a:2:c".to_string()));
+        assert!(features.contains(&"b:1:c".to_string()));
+    }
+
+    #[test]
+    fn short_input_falls_back_to_unigrams() {
+        let features = BayesClassifier::osb_features("hello");
+        assert_eq!(features, vec!["hello".to_string()]);
+    }
+}
@@ -1,66 +1,207 @@
 // src-tauri/src/analyzer/content_analyzer.rs
 use crate::clipboard::types::{
-    BasicContentType, CompleteAnalysis, RuleAnalysis, AiAnalysis, 
-    MergedActionSuggestion, ClipboardError
+    AiActionSuggestion, BasicContentType, CompleteAnalysis, RuleAnalysis, AiAnalysis,
+    MergedActionSuggestion, ClipboardError, ClipboardEvent, UserContext,
 };
 use crate::analyzer::rule_engine::RuleEngine;
 use crate::analyzer::ai_engine::AiEngine;
+use crate::analyzer::genie_engine::GenieEngine;
+use crate::analyzer::feedback::ActionFeedbackStore;
+use crate::clipboard::content_detector::CLOSE_SCORE_MARGIN;
+use crate::history::{ClipboardHistoryStore, HashEmbedder};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::OnceCell;
 use log::{info, warn};
 
+/// How many past clips `find_similar` contributes as context for the
+/// current AI intent prediction.
+const SIMILAR_HISTORY_LIMIT: usize = 3;
+
+/// Sensitivity (see `RuleAnalysis::sensitivity`) at or above which content
+/// is treated as carrying PII/secrets: its redacted form is sent to the AI
+/// engine and stored into history instead of the raw content, and it's
+/// dropped from history entirely when `block_sensitive_from_history` is set.
+const SENSITIVITY_THRESHOLD: f32 = 0.6;
+
+/// How much `merge_suggestions`'s ranking trusts the learned selection
+/// rate (see `ActionFeedbackStore`) vs. the rule/AI call's own confidence
+/// for this particular clip. Tunable: higher trusts the user's track
+/// record more, lower lets a single high-confidence prediction dominate.
+const FEEDBACK_RANKING_WEIGHT: f32 = 0.4;
+
 pub struct ContentAnalyzer {
     rule_engine: RuleEngine,
     pub ai_engine: AiEngine,
+    /// On-device fallback tried when `ai_engine` errors out (unreachable
+    /// endpoint, bad API key, etc.) - starts a pool of Genie worker
+    /// processes (and, if those also fail, a local rust-bert pipeline) the
+    /// first time it's actually needed rather than on every launch.
+    genie_engine: OnceCell<GenieEngine>,
     ai_timeout_ms: u64,
+    /// `None` if the on-disk history database couldn't be opened; analysis
+    /// still works, it just loses similar-clip context and recall.
+    history: Option<ClipboardHistoryStore>,
+    /// `None` if the on-disk feedback database couldn't be opened;
+    /// `merge_suggestions` then ranks by confidence alone, same as before
+    /// this store existed.
+    feedback: Option<ActionFeedbackStore>,
+    /// If true, clips flagged sensitive are never written to history at
+    /// all (not even redacted). Configurable via
+    /// `CLIPMIND_BLOCK_SENSITIVE_HISTORY=1` since some users would rather
+    /// keep a masked record than lose it outright.
+    block_sensitive_from_history: bool,
 }
 
 impl ContentAnalyzer {
     pub fn new() -> Self {
+        let history = match ClipboardHistoryStore::open(
+            "clipmind_history.db",
+            Arc::new(HashEmbedder::default()),
+            500,
+        ) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                warn!("Failed to open clipboard history store, continuing without it: {}", e);
+                None
+            }
+        };
+
+        let feedback = match ActionFeedbackStore::open("clipmind_action_feedback.db") {
+            Ok(store) => Some(store),
+            Err(e) => {
+                warn!("Failed to open action feedback store, continuing without re-ranking: {}", e);
+                None
+            }
+        };
+
+        let block_sensitive_from_history = std::env::var("CLIPMIND_BLOCK_SENSITIVE_HISTORY")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
         Self {
             rule_engine: RuleEngine::new(),
             ai_engine: AiEngine::new(),
+            genie_engine: OnceCell::new(),
             ai_timeout_ms: 10000,
+            history,
+            feedback,
+            block_sensitive_from_history,
         }
     }
 
-    
+    /// Returns the on-device fallback engine, starting it on first use.
+    async fn genie(&self) -> &GenieEngine {
+        self.genie_engine.get_or_init(GenieEngine::new).await
+    }
+
+
     pub async fn analyze_content(
         &self,
         content: &str,
         content_type: BasicContentType,
+    ) -> Result<CompleteAnalysis, ClipboardError> {
+        self.analyze_content_ranked(content, &[(content_type, 1.0)]).await
+    }
+
+    /// Same as `analyze_content`, but takes `ContentDetector::detect_ranked`'s
+    /// full output instead of a single type. The top-scored type still
+    /// drives the rule engine as before; when the runner-up is within
+    /// `CLOSE_SCORE_MARGIN` of it, it's passed to the AI engine as a
+    /// secondary candidate (see `UserContext::secondary_candidate`) instead
+    /// of being discarded.
+    pub async fn analyze_content_ranked(
+        &self,
+        content: &str,
+        ranked_types: &[(BasicContentType, f32)],
     ) -> Result<CompleteAnalysis, ClipboardError> {
         let start_time = Instant::now();
-        
+
+        let (content_type, top_score) = ranked_types
+            .first()
+            .cloned()
+            .unwrap_or((BasicContentType::PlainText, 0.0));
+        let secondary_candidate = ranked_types
+            .get(1)
+            .filter(|(_, score)| top_score - *score <= CLOSE_SCORE_MARGIN)
+            .map(|(candidate_type, score)| format!("{:?} ({:.2})", candidate_type, score));
+
         info!("Start analyzing content, type: {:?}, length: {}", content_type, content.len());
-        
+
         // Phase 1: Rule engine analysis (fast, must complete)
         let rule_analysis = self.rule_engine.analyze(content, content_type.clone());
         info!("Rule analysis completed, confidence: {:.2}", rule_analysis.confidence);
-        
+
+        // Sensitive content never leaves the machine: the AI engine and the
+        // history store both see the redacted form when one was produced.
+        let safe_content = rule_analysis.redacted_content.as_deref().unwrap_or(content);
+        let is_sensitive = rule_analysis.sensitivity >= SENSITIVITY_THRESHOLD;
+
         // Phase 2: AI intent prediction (optional, with timeout)
         let ai_analysis = if rule_analysis.needs_ai_analysis {
             info!("Triggering AI intent prediction...");
-            match self.predict_ai_intent(content, &content_type).await {
+            let mut context = self.similar_history_context(safe_content);
+            if secondary_candidate.is_some() {
+                let context = context.get_or_insert_with(|| UserContext {
+                    recent_actions: Vec::new(),
+                    time_of_day: String::new(),
+                    app_context: None,
+                    secondary_candidate: None,
+                });
+                context.secondary_candidate = secondary_candidate;
+            }
+            match self.predict_ai_intent(safe_content, &content_type, context).await {
                 Ok(analysis) => {
                     info!("AI analysis completed, predicted {} actions", analysis.intent_predictions.len());
                     Some(analysis)
                 },
                 Err(e) => {
-                    warn!("AI analysis failed, continue using rule analysis: {}", e);
-                    None
+                    warn!("AI analysis failed, trying on-device fallback: {}", e);
+                    match self.genie().await.predict_intent(safe_content, &content_type).await {
+                        Ok(predictions) if !predictions.is_empty() => {
+                            info!("On-device fallback predicted {} actions", predictions.len());
+                            Some(Self::suggestions_to_analysis(predictions))
+                        }
+                        Ok(_) => {
+                            info!("On-device fallback returned no suggestions, continue using rule analysis");
+                            None
+                        }
+                        Err(genie_err) => {
+                            warn!("On-device fallback also failed, continue using rule analysis: {}", genie_err);
+                            None
+                        }
+                    }
                 }
             }
         } else {
             info!("Skipping AI analysis (rule engine already provides sufficient suggestions)");
             None
         };
-        
+
         // Phase 3: Merge suggestions
-        let merged_actions = self.merge_suggestions(&rule_analysis, &ai_analysis);
-        
+        let merged_actions = self.merge_suggestions(&rule_analysis, &ai_analysis, &content_type);
+
+        if let Some(feedback) = &self.feedback {
+            for action in &merged_actions {
+                feedback.record_offered(&content_type, &action.id);
+            }
+        }
+
+        if let Some(history) = &self.history {
+            if is_sensitive && self.block_sensitive_from_history {
+                info!("Sensitive clip blocked from history (CLIPMIND_BLOCK_SENSITIVE_HISTORY=1)");
+            } else {
+                let event = ClipboardEvent::new(safe_content.to_string(), content_type.clone(), None);
+                if let Err(e) = history.record(&event) {
+                    warn!("Failed to record clip into history: {}", e);
+                }
+            }
+        }
+
         let processing_time = start_time.elapsed().as_millis() as u64;
         info!("Content analysis completed, total time: {}ms", processing_time);
-        
+
         Ok(CompleteAnalysis {
             rule_analysis,
             ai_analysis,
@@ -69,6 +210,42 @@ impl ContentAnalyzer {
         })
     }
 
+    /// Look up similar past clips to hand the AI engine as extra context,
+    /// `None` if there's no history store or nothing similar was found.
+    fn similar_history_context(&self, content: &str) -> Option<UserContext> {
+        let history = self.history.as_ref()?;
+        let similar = history.find_similar(content, SIMILAR_HISTORY_LIMIT);
+
+        if similar.is_empty() {
+            return None;
+        }
+
+        Some(UserContext {
+            recent_actions: similar.into_iter().map(|event| event.content).collect(),
+            time_of_day: String::new(),
+            app_context: None,
+            secondary_candidate: None,
+        })
+    }
+
+    /// Averages `predictions`' confidence the same way `predict_ai_intent`
+    /// and `GenieEngine::analyze` both do, so either source of suggestions
+    /// ends up in an `AiAnalysis` the same shape.
+    fn suggestions_to_analysis(predictions: Vec<AiActionSuggestion>) -> AiAnalysis {
+        let confidence = if !predictions.is_empty() {
+            predictions.iter().map(|p| p.confidence).sum::<f32>() / predictions.len() as f32
+        } else {
+            0.0
+        };
+
+        AiAnalysis {
+            intent_predictions: predictions,
+            summary: None,
+            confidence,
+            raw_response: None,
+        }
+    }
+
     // Public method for processing AI tasks
     pub async fn process_ai_task(
         &self,
@@ -76,7 +253,66 @@ impl ContentAnalyzer {
         task_type: &str,
         parameters: Option<std::collections::HashMap<String, String>>,
     ) -> Result<String, String> {
-        self.ai_engine.process_ai_task(content, task_type, parameters).await
+        match self.ai_engine.process_ai_task(content, task_type, parameters.clone()).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                warn!("AI task '{}' failed via cloud engine, trying on-device fallback: {}", task_type, e);
+                self.genie().await.process_ai_task(content, task_type, parameters).await
+            }
+        }
+    }
+
+    /// Streaming counterpart to `process_ai_task`: same prompt/model
+    /// selection, but hands back a boxed stream of incremental chunks so
+    /// callers can forward partial text to the frontend as it arrives
+    /// instead of waiting for the whole completion.
+    pub async fn process_ai_task_stream(
+        &self,
+        content: &str,
+        task_type: &str,
+        parameters: Option<std::collections::HashMap<String, String>>,
+    ) -> Result<std::pin::Pin<Box<dyn futures_util::Stream<Item = Result<String, String>> + Send>>, String> {
+        let stream = self.ai_engine.process_ai_task_stream(content, task_type, parameters).await?;
+        Ok(Box::pin(stream))
+    }
+
+    /// Retrieval-augmented Q&A over clipboard history; see
+    /// `AiEngine::ask_history`. Returns an error if there's no history
+    /// store to search (e.g. it failed to open at startup).
+    pub async fn ask_history(&self, query: &str) -> Result<crate::clipboard::types::RagAnswer, String> {
+        let history = self
+            .history
+            .as_ref()
+            .ok_or_else(|| "Clipboard history is unavailable".to_string())?;
+        self.ai_engine.ask_history(query, history, None, None).await
+    }
+
+    /// Runs `process_ai_task` over many `(content, task_type)` pairs
+    /// concurrently; see `AiEngine::process_ai_tasks_batch`.
+    pub async fn process_ai_tasks_batch(
+        &self,
+        items: Vec<(String, String)>,
+    ) -> Vec<Result<String, String>> {
+        self.ai_engine.process_ai_tasks_batch(items).await
+    }
+
+    /// Multi-step tool-calling over `content`; see `AiEngine::run_agentic_task`.
+    pub async fn run_agentic_task(
+        &self,
+        content: &str,
+        basic_type: &BasicContentType,
+    ) -> Result<String, String> {
+        self.ai_engine.run_agentic_task(content, basic_type, self.history.as_ref()).await
+    }
+
+    /// Runs a chain of AI tasks, each step's output feeding the next step's
+    /// input (e.g. summarize then translate).
+    pub async fn process_ai_pipeline(
+        &self,
+        content: &str,
+        steps: Vec<crate::clipboard::types::PipelineStep>,
+    ) -> Result<crate::clipboard::types::PipelineResult, String> {
+        self.ai_engine.process_ai_pipeline(content, steps).await
     }
 
     /// AI intent prediction (with timeout)
@@ -84,10 +320,13 @@ impl ContentAnalyzer {
         &self,
         content: &str,
         content_type: &BasicContentType,
+        context: Option<UserContext>,
     ) -> Result<AiAnalysis, ClipboardError> {
         // Wrap AI call with tokio::time::timeout
-        let ai_future = self.ai_engine.predict_intent(content, content_type);
-        
+        let ai_future = self
+            .ai_engine
+            .predict_intent_with_context(content, content_type, context.as_ref());
+
         match tokio::time::timeout(
             tokio::time::Duration::from_millis(self.ai_timeout_ms),
             ai_future
@@ -111,37 +350,120 @@ impl ContentAnalyzer {
         }
     }
 
-    /// Merge rule and AI suggestions
+    /// Merge rule and AI suggestions into one deduped-by-id list, then rank
+    /// by a blend of this call's confidence and the learned `selection_rate`
+    /// (see `ActionFeedbackStore`) so actions the user has actually been
+    /// choosing outrank ones a rule/AI call merely happens to be confident
+    /// about right now. Hotkeys are assigned in final rank order.
     fn merge_suggestions(
         &self,
         rule_analysis: &RuleAnalysis,
         ai_analysis: &Option<AiAnalysis>,
+        content_type: &BasicContentType,
     ) -> Vec<MergedActionSuggestion> {
-        let mut merged = Vec::new();
-        let mut hotkey_counter = 1;
+        let mut by_id: HashMap<String, MergedActionSuggestion> = HashMap::new();
 
-        // 1. Add high-confidence rule suggestions
+        // 1. High-confidence rule suggestions
         for action in &rule_analysis.suggested_actions {
             if action.confidence >= 0.8 {
-                merged.push(MergedActionSuggestion {
-                    id: action.id.clone(),
-                    label: action.label.clone(),
-                    icon: action.icon.clone(),
-                    action_type: "rule".to_string(),
-                    hotkey: hotkey_counter.to_string(),
-                    confidence: action.confidence,
-                    source: "rule_engine".to_string(),
-                    reason: Some("Based on rule matching".to_string()),
-                    estimated_time: action.estimated_time,
-                    parameters: action.parameters.clone(),
-                });
-                hotkey_counter += 1;
+                by_id.insert(
+                    action.id.clone(),
+                    MergedActionSuggestion {
+                        id: action.id.clone(),
+                        label: action.label.clone(),
+                        icon: action.icon.clone(),
+                        action_type: "rule".to_string(),
+                        hotkey: String::new(),
+                        confidence: action.confidence,
+                        source: "rule_engine".to_string(),
+                        reason: Some("Based on rule matching".to_string()),
+                        estimated_time: action.estimated_time,
+                        parameters: action.parameters.clone(),
+                    },
+                );
             }
         }
 
+        // 2. AI intent predictions - merged in on top, deduped by id with
+        // whichever source (rule or AI) is more confident winning, and
+        // tagged "merged"/"hybrid" when both sources agreed on the same id.
+        if let Some(ai) = ai_analysis {
+            for prediction in &ai.intent_predictions {
+                match by_id.get_mut(&prediction.action_id) {
+                    Some(existing) => {
+                        existing.action_type = "hybrid".to_string();
+                        existing.source = "merged".to_string();
+                        if prediction.confidence > existing.confidence {
+                            existing.confidence = prediction.confidence;
+                            existing.reason = prediction.reason.clone();
+                        }
+                    }
+                    None => {
+                        by_id.insert(
+                            prediction.action_id.clone(),
+                            MergedActionSuggestion {
+                                id: prediction.action_id.clone(),
+                                label: prediction.label.clone(),
+                                icon: prediction.icon.clone(),
+                                action_type: "ai".to_string(),
+                                hotkey: String::new(),
+                                confidence: prediction.confidence,
+                                source: "ai_engine".to_string(),
+                                reason: prediction.reason.clone(),
+                                estimated_time: None,
+                                parameters: prediction.parameters.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut merged: Vec<MergedActionSuggestion> = by_id.into_values().collect();
+
+        merged.sort_by(|a, b| {
+            self.ranking_score(content_type, b)
+                .partial_cmp(&self.ranking_score(content_type, a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for (i, action) in merged.iter_mut().enumerate() {
+            action.hotkey = (i + 1).to_string();
+        }
+
         merged
     }
 
+    /// Blends `action.confidence` with its learned `selection_rate` for
+    /// `content_type` (neutral 0.5 if there's no feedback store or no
+    /// history yet for this id), weighted by `FEEDBACK_RANKING_WEIGHT`.
+    fn ranking_score(&self, content_type: &BasicContentType, action: &MergedActionSuggestion) -> f32 {
+        let selection_rate = self
+            .feedback
+            .as_ref()
+            .map(|store| store.selection_rate(content_type, &action.id))
+            .unwrap_or(0.5);
+
+        (1.0 - FEEDBACK_RANKING_WEIGHT) * action.confidence + FEEDBACK_RANKING_WEIGHT * selection_rate
+    }
+
+    /// Teaches the local `RuleEngine` bayes classifier that `content`
+    /// belongs to `category`, so future `PlainText` clips like it are
+    /// recognized without an AI round trip. Call this whenever the user
+    /// accepts/confirms a category for a clip (e.g. picking a suggestion).
+    pub fn train_category(&self, content: &str, category: &str) {
+        self.rule_engine.train(content, category);
+    }
+
+    /// Records that the user chose `action_id` for this content type, so
+    /// future `merge_suggestions` calls rank it higher; no-op if the
+    /// feedback store failed to open at startup.
+    pub fn record_action_chosen(&self, content_type: &BasicContentType, action_id: &str) {
+        if let Some(feedback) = &self.feedback {
+            feedback.record_chosen(content_type, action_id);
+        }
+    }
+
     /// Test AI engine connection
     pub async fn test_ai_connection(&self) -> bool {
         match self.ai_engine.test_connection().await {
@@ -0,0 +1,138 @@
+// src-tauri/src/analyzer/feedback.rs
+//! Persistent memory of which merged suggestion the user actually picked,
+//! per `BasicContentType`, so `ContentAnalyzer::merge_suggestions` can rank
+//! frequently-chosen actions ahead of ones a rule/AI call happens to be
+//! confident about just this once.
+//!
+//! Modeled on `history::ClipboardHistoryStore`'s SQLite-backed design: a
+//! tiny `(content_type, action_id) -> (offers, accepts)` table, with
+//! `selection_rate` turning those counts into a 0.0-1.0 rate using additive
+//! (Laplace) smoothing so a brand-new action isn't stuck at an unearned 0.0
+//! or 1.0 after a single observation.
+
+use crate::clipboard::types::{BasicContentType, ClipboardError};
+use rusqlite::{params, Connection};
+use std::sync::Mutex;
+
+pub struct ActionFeedbackStore {
+    conn: Mutex<Connection>,
+}
+
+impl ActionFeedbackStore {
+    pub fn open(db_path: &str) -> Result<Self, ClipboardError> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to open feedback db: {}", e)))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS action_feedback (
+                content_type TEXT NOT NULL,
+                action_id TEXT NOT NULL,
+                offers INTEGER NOT NULL DEFAULT 0,
+                accepts INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (content_type, action_id)
+            )",
+            [],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to create feedback table: {}", e)))?;
+
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Records that `action_id` was shown to the user for this content
+    /// type, so `selection_rate` has a denominator to divide by. Called for
+    /// every merged suggestion `ContentAnalyzer::analyze_content` returns.
+    pub fn record_offered(&self, content_type: &BasicContentType, action_id: &str) {
+        self.upsert(content_type, action_id, 1, 0);
+    }
+
+    /// Records that the user actually chose `action_id` for this content
+    /// type; see `ContentAnalyzer::record_action_chosen`.
+    pub fn record_chosen(&self, content_type: &BasicContentType, action_id: &str) {
+        self.upsert(content_type, action_id, 0, 1);
+    }
+
+    fn upsert(&self, content_type: &BasicContentType, action_id: &str, offer_delta: i64, accept_delta: i64) {
+        let key = Self::content_type_key(content_type);
+        let conn = self.conn.lock().unwrap();
+        let result = conn.execute(
+            "INSERT INTO action_feedback (content_type, action_id, offers, accepts) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(content_type, action_id) DO UPDATE SET
+                offers = offers + ?3,
+                accepts = accepts + ?4",
+            params![key, action_id, offer_delta, accept_delta],
+        );
+        if let Err(e) = result {
+            log::warn!("Failed to record action feedback ({}/{}): {}", key, action_id, e);
+        }
+    }
+
+    /// `(accepts + 1) / (offers + 2)` - Laplace-smoothed selection rate, so
+    /// an action that's never been offered reads as a neutral 0.5 instead
+    /// of an unearned 0.0, and one accepted every single time it's offered
+    /// still leaves a little room below 1.0.
+    pub fn selection_rate(&self, content_type: &BasicContentType, action_id: &str) -> f32 {
+        let key = Self::content_type_key(content_type);
+        let conn = self.conn.lock().unwrap();
+
+        let row: Option<(i64, i64)> = conn
+            .query_row(
+                "SELECT offers, accepts FROM action_feedback WHERE content_type = ?1 AND action_id = ?2",
+                params![key, action_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .ok();
+
+        let (offers, accepts) = row.unwrap_or((0, 0));
+        (accepts as f32 + 1.0) / (offers as f32 + 2.0)
+    }
+
+    fn content_type_key(content_type: &BasicContentType) -> String {
+        format!("{:?}", content_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> ActionFeedbackStore {
+        let path = format!(
+            "{}/clipmind_feedback_test_{}.db",
+            std::env::temp_dir().display(),
+            uuid::Uuid::new_v4()
+        );
+        ActionFeedbackStore::open(&path).unwrap()
+    }
+
+    #[test]
+    fn unseen_action_is_neutral() {
+        let store = temp_store();
+        let rate = store.selection_rate(&BasicContentType::Url, "open_browser");
+        assert!((rate - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn selection_rate_rises_with_accepts() {
+        let store = temp_store();
+        for _ in 0..10 {
+            store.record_offered(&BasicContentType::Url, "open_browser");
+        }
+        for _ in 0..8 {
+            store.record_chosen(&BasicContentType::Url, "open_browser");
+        }
+
+        let rate = store.selection_rate(&BasicContentType::Url, "open_browser");
+        assert!((rate - (9.0 / 12.0)).abs() < 1e-6, "rate was {}", rate);
+    }
+
+    #[test]
+    fn content_types_are_tracked_independently() {
+        let store = temp_store();
+        store.record_offered(&BasicContentType::Url, "shared_id");
+        store.record_chosen(&BasicContentType::Url, "shared_id");
+
+        let url_rate = store.selection_rate(&BasicContentType::Url, "shared_id");
+        let code_rate = store.selection_rate(&BasicContentType::Code, "shared_id");
+        assert!(url_rate > code_rate);
+    }
+}
@@ -1,32 +1,314 @@
 // src-tauri/src/analyzer/genie_engine.rs
 
 use crate::clipboard::types::{
-    AiAnalysis, AiActionSuggestion, AiActionType, 
+    AiAnalysis, AiActionSuggestion, AiActionType,
     BasicContentType, RuleAnalysis
 };
+use crate::analyzer::local_nlp::{self, AnalysisBackend};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command as TokioCommand};
+use tokio::sync::{mpsc, Mutex as AsyncMutex};
 use log::{info, warn, error};
-use std::path::PathBuf;
+
+/// One long-lived `genie-t2t-run.exe --interactive` child process with its
+/// stdin/stdout piped, so prompts can be written and `[BEGIN]:`...`[END]`
+/// framed responses read back without reloading the model every time.
+struct GenieWorker {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl GenieWorker {
+    async fn spawn(bundle_path: &Path) -> Result<Self, String> {
+        let genie_exe = bundle_path.join("genie-t2t-run.exe");
+        let config_file = bundle_path.join("genie_config.json");
+
+        let mut child = TokioCommand::new(&genie_exe)
+            .arg("-c")
+            .arg(&config_file)
+            .arg("--interactive")
+            .current_dir(bundle_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to spawn Genie worker: {}", e))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "Genie worker has no stdin".to_string())?;
+        let stdout = BufReader::new(
+            child
+                .stdout
+                .take()
+                .ok_or_else(|| "Genie worker has no stdout".to_string())?,
+        );
+
+        Ok(Self { child, stdin, stdout })
+    }
+
+    /// Writes `prompt` on one line and reads lines back until `[END]`
+    /// appears, then extracts the text between `[BEGIN]:` and `[END]`.
+    async fn send_prompt(&mut self, prompt: &str) -> Result<String, String> {
+        self.stdin
+            .write_all(format!("{}\n", prompt.replace('\n', " ")).as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write prompt to Genie worker: {}", e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to flush Genie worker stdin: {}", e))?;
+
+        let mut buffer = String::new();
+        loop {
+            let mut line = String::new();
+            let bytes_read = self
+                .stdout
+                .read_line(&mut line)
+                .await
+                .map_err(|e| format!("Failed to read Genie worker stdout: {}", e))?;
+            if bytes_read == 0 {
+                return Err("Genie worker closed stdout unexpectedly".to_string());
+            }
+            buffer.push_str(&line);
+            if line.contains("[END]") {
+                break;
+            }
+        }
+
+        match (buffer.find("[BEGIN]:"), buffer.find("[END]")) {
+            (Some(start), Some(end)) if start + 8 <= end => {
+                Ok(buffer[start + 8..end].trim().to_string())
+            }
+            _ => Err("Genie worker response missing [BEGIN]/[END] markers".to_string()),
+        }
+    }
+
+    /// Health check: `true` as long as the child hasn't exited.
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+/// Pool of long-lived `GenieWorker`s that keep the model resident in
+/// memory, handed out over an mpsc channel so concurrent `predict_intent`/
+/// `process_ai_task` calls aren't serialized behind one process. A worker
+/// found dead (or that dies mid-request) is respawned before being
+/// returned to the pool; if respawning fails the pool just shrinks rather
+/// than panicking.
+struct GenieWorkerPool {
+    idle_tx: mpsc::Sender<GenieWorker>,
+    idle_rx: AsyncMutex<mpsc::Receiver<GenieWorker>>,
+    bundle_path: PathBuf,
+}
+
+impl GenieWorkerPool {
+    /// Spawns up to `size` workers. Returns `None` if not a single one
+    /// could be started (e.g. Genie doesn't support `--interactive`),
+    /// signaling callers to use the one-shot fallback instead.
+    async fn new(bundle_path: PathBuf, size: usize) -> Option<Self> {
+        let (idle_tx, idle_rx) = mpsc::channel(size.max(1));
+        let mut spawned = 0;
+
+        for _ in 0..size {
+            match GenieWorker::spawn(&bundle_path).await {
+                Ok(worker) => {
+                    if idle_tx.send(worker).await.is_ok() {
+                        spawned += 1;
+                    }
+                }
+                Err(e) => warn!("Failed to start a Genie worker: {}", e),
+            }
+        }
+
+        if spawned == 0 {
+            return None;
+        }
+
+        info!("Genie worker pool ready with {}/{} worker(s)", spawned, size);
+        Some(Self {
+            idle_tx,
+            idle_rx: AsyncMutex::new(idle_rx),
+            bundle_path,
+        })
+    }
+
+    async fn submit(&self, prompt: &str) -> Result<String, String> {
+        let mut worker = {
+            let mut rx = self.idle_rx.lock().await;
+            rx.recv()
+                .await
+                .ok_or_else(|| "Genie worker pool has no workers left".to_string())?
+        };
+
+        if !worker.is_alive() {
+            warn!("Genie worker found dead, respawning before use");
+            worker = GenieWorker::spawn(&self.bundle_path).await?;
+        }
+
+        let result = worker.send_prompt(prompt).await;
+
+        // Keep the pool at capacity: return the worker if it's still
+        // healthy, otherwise respawn a replacement for the next caller.
+        let returning = if result.is_ok() && worker.is_alive() {
+            Some(worker)
+        } else {
+            warn!("Genie worker unhealthy after use, respawning a replacement");
+            GenieWorker::spawn(&self.bundle_path).await.ok()
+        };
+        if let Some(w) = returning {
+            let _ = self.idle_tx.send(w).await;
+        }
+
+        result
+    }
+}
+
+/// A source or target language for translation, as an NLLB/FLORES-200
+/// style (language, script) pair rather than a single "is this English"
+/// flag, so `detect_language`/`process_ai_task` can handle more than just
+/// English<->Chinese.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    English,
+    TraditionalChinese,
+    SimplifiedChinese,
+    Japanese,
+    Korean,
+    French,
+    Spanish,
+    German,
+    Other,
+}
+
+impl Lang {
+    /// Parses a BCP-47-ish code (`"en"`, `"zh-Hant"`, `"ja"`, ...) as
+    /// typically supplied via a `target_lang` task parameter. Unrecognized
+    /// codes return `None` so the caller can fall back to a sane default
+    /// instead of silently mistranslating.
+    fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" | "eng" | "en-us" | "en-gb" => Some(Lang::English),
+            "zh-hant" | "zh-tw" | "zh-hk" | "zh_hant" => Some(Lang::TraditionalChinese),
+            "zh-hans" | "zh-cn" | "zh" | "zh_hans" => Some(Lang::SimplifiedChinese),
+            "ja" | "jpn" => Some(Lang::Japanese),
+            "ko" | "kor" => Some(Lang::Korean),
+            "fr" | "fra" => Some(Lang::French),
+            "es" | "spa" => Some(Lang::Spanish),
+            "de" | "deu" => Some(Lang::German),
+            _ => None,
+        }
+    }
+
+    /// Default translation target when the caller didn't ask for a
+    /// specific one: Chinese for English source (the app's original
+    /// behavior), English for everything else.
+    fn default_translation_target(&self) -> Self {
+        match self {
+            Lang::English => Lang::TraditionalChinese,
+            _ => Lang::English,
+        }
+    }
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            Lang::English => "English",
+            Lang::TraditionalChinese => "Traditional Chinese",
+            Lang::SimplifiedChinese => "Simplified Chinese",
+            Lang::Japanese => "Japanese",
+            Lang::Korean => "Korean",
+            Lang::French => "French",
+            Lang::Spanish => "Spanish",
+            Lang::German => "German",
+            Lang::Other => "English",
+        }
+    }
+
+    /// BCP-47-ish code for this language, the inverse of `from_code` - used
+    /// to hand the chosen target language on to the on-device fallback
+    /// (`local_nlp::RustBertBackend`), which needs its own `Language` token
+    /// rather than Genie's natural-language prompt text.
+    fn code(&self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::TraditionalChinese => "zh-Hant",
+            Lang::SimplifiedChinese => "zh-Hans",
+            Lang::Japanese => "ja",
+            Lang::Korean => "ko",
+            Lang::French => "fr",
+            Lang::Spanish => "es",
+            Lang::German => "de",
+            Lang::Other => "en",
+        }
+    }
+}
+
+/// Default translate target when the caller didn't pass an explicit
+/// `target_lang` parameter: the user's OS locale (`CLIPMIND_LOCALE`,
+/// falling back to the POSIX `LC_ALL`/`LANG` env vars) when it names a
+/// language we recognize and it differs from the detected source,
+/// otherwise the app's original English<->Chinese default.
+fn default_target_lang(source_lang: Lang) -> Lang {
+    let locale = std::env::var("CLIPMIND_LOCALE")
+        .or_else(|_| std::env::var("LC_ALL"))
+        .or_else(|_| std::env::var("LANG"))
+        .ok();
+
+    if let Some(locale) = locale {
+        // e.g. "en_US.UTF-8" -> "en-US"
+        let lang_tag = locale.split('.').next().unwrap_or(&locale).replace('_', "-");
+        if let Some(target) = Lang::from_code(&lang_tag) {
+            if target != source_lang {
+                return target;
+            }
+        }
+    }
+
+    source_lang.default_translation_target()
+}
 
 pub struct GenieEngine {
     genie_bundle_path: PathBuf,
     model_name: String,
     timeout_ms: u64,
+    /// `None` when interactive mode couldn't be started; `call_genie` then
+    /// falls back to spawning a one-shot `genie-t2t-run.exe` per call.
+    pool: Option<GenieWorkerPool>,
+    /// On-device backend (rust-bert when compiled in, a stub otherwise)
+    /// tried when Genie itself fails, so a missing bundle degrades to a
+    /// real result instead of a canned suggestion list.
+    local_backend: Box<dyn AnalysisBackend>,
 }
 
 impl GenieEngine {
-    pub fn new() -> Self {
+    pub async fn new() -> Self {
         //let genie_bundle_path = PathBuf::from("./genie_bundle");
         let genie_bundle_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-    .join("genie_bundle");
+            .join("genie_bundle");
+
+        // One worker per CPU keeps the model resident without
+        // oversubscribing the machine; `num_cpus`-style sizing via the
+        // standard library so we don't need an extra dependency for it.
+        let pool_size = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+        let pool = GenieWorkerPool::new(genie_bundle_path.clone(), pool_size).await;
+        if pool.is_none() {
+            warn!("Genie interactive mode unavailable, falling back to one-shot invocations");
+        }
 
-        
         Self {
             genie_bundle_path,
             model_name: "phi-3.5-mini".to_string(),
             timeout_ms: 100000,
+            pool,
+            local_backend: local_nlp::default_backend(),
         }
     }
 
@@ -37,9 +319,9 @@ impl GenieEngine {
         basic_type: &BasicContentType,
     ) -> Result<Vec<AiActionSuggestion>, String> {
         info!("使用 Genie 開始 AI 意圖預測，內容類型: {:?}", basic_type);
-        
+
         let prompt = self.build_intelligent_prompt(content, basic_type);
-        
+
         // 調用 Genie
         let response = match self.call_genie(&prompt).await {
             Ok(resp) => {
@@ -47,13 +329,20 @@ impl GenieEngine {
                 resp
             },
             Err(e) => {
-                warn!("Genie 調用失敗: {}", e);
-                return Ok(self.get_fallback_suggestions(basic_type));
+                warn!("Genie 調用失敗: {}，嘗試本機 NLP 後備", e);
+                return match self.local_backend.predict_intent(content, basic_type).await {
+                    Ok(suggestions) if !suggestions.is_empty() => Ok(suggestions),
+                    Ok(_) => Ok(self.get_fallback_suggestions(basic_type)),
+                    Err(local_err) => {
+                        warn!("本機 NLP 後備也失敗: {}", local_err);
+                        Ok(self.get_fallback_suggestions(basic_type))
+                    }
+                };
             }
         };
-        
+
         let suggestions = self.parse_ai_response(&response, basic_type);
-        
+
         info!("AI 預測完成: {} 個建議", suggestions.len());
         Ok(suggestions)
     }
@@ -62,15 +351,28 @@ impl GenieEngine {
         &self,
         content: &str,
         task_type: &str,
-        _parameters: Option<HashMap<String, String>>,
+        parameters: Option<HashMap<String, String>>,
     ) -> Result<String, String> {
         info!("Process AI task: {}", task_type);
-        
+
+        // Carried out of the `"translate"` match arm so the local-fallback
+        // call below can give `RustBertBackend` the same target language
+        // Genie's prompt was built for, instead of it always assuming
+        // English->Mandarin.
+        let mut translate_target_lang: Option<&'static str> = None;
+
         let prompt = match task_type {
             "translate" => {
+                let source_lang = self.detect_language(content);
+                let target_lang = parameters
+                    .as_ref()
+                    .and_then(|p| p.get("target_lang"))
+                    .and_then(|code| Lang::from_code(code))
+                    .unwrap_or_else(|| default_target_lang(source_lang));
+                translate_target_lang = Some(target_lang.code());
                 format!(
-                    "<|begin_of_text|><|start_header_id|>user<|end_header_id|>\n\nTranslate the following text to Traditional Chinese. Only return the translation:\n\n{}\n<|eot_id|><|start_header_id|>assistant<|end_header_id|>",
-                    content
+                    "<|begin_of_text|><|start_header_id|>user<|end_header_id|>\n\nTranslate the following {} text to {}. Only return the translation:\n\n{}\n<|eot_id|><|start_header_id|>assistant<|end_header_id|>",
+                    source_lang.display_name(), target_lang.display_name(), content
                 )
             },
             "summarize" | "summarize_webpage" => {
@@ -110,24 +412,34 @@ impl GenieEngine {
                 )
             }
         };
-        
-        let response = self.call_genie(&prompt).await?;
-        Ok(response)
+
+        match self.call_genie(&prompt).await {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                warn!("Genie 調用失敗: {}，嘗試本機 NLP 後備執行 {}", e, task_type);
+                self.local_backend.process_ai_task(content, task_type, translate_target_lang).await
+            }
+        }
     }
 
-    // Test Genie Connect 
+    // Test Genie Connect
     pub async fn test_connection(&self) -> Result<bool, String> {
-        // Check if genie-t2t-run.exe exists
+        // The pool already proved Genie works by spawning at least one
+        // interactive worker at startup.
+        if self.pool.is_some() {
+            return Ok(true);
+        }
+
+        // No pool: fall back to checking the one-shot executable exists
+        // and can answer a trivial prompt.
         let genie_exe = self.genie_bundle_path.join("genie-t2t-run.exe");
-        
         if !genie_exe.exists() {
             return Err(format!("Genie executable not found at: {:?}", genie_exe));
         }
-        
-        // Test a simple question first
+
         let test_prompt = "<|begin_of_text|><|start_header_id|>user<|end_header_id|>\n\nWhat is France's capital?<|eot_id|><|start_header_id|>assistant<|end_header_id|>";
-        
-        match self.call_genie(&test_prompt).await {
+
+        match self.call_genie_one_shot(test_prompt).await {
             Ok(_) => {
                 info!("Genie 連接測試成功");
                 Ok(true)
@@ -139,19 +451,39 @@ impl GenieEngine {
         }
     }
 
-
+    /// Runs `prompt` through the worker pool when it's available, falling
+    /// back to a one-shot `genie-t2t-run.exe` invocation (the old
+    /// behavior) when Genie doesn't support interactive mode.
     async fn call_genie(&self, prompt: &str) -> Result<String, String> {
+        if let Some(pool) = &self.pool {
+            return match tokio::time::timeout(
+                std::time::Duration::from_millis(self.timeout_ms),
+                pool.submit(prompt),
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(_) => Err(format!("Genie worker timed out after {}ms", self.timeout_ms)),
+            };
+        }
+
+        self.call_genie_one_shot(prompt).await
+    }
+
+    /// Spawns a fresh `genie-t2t-run.exe` process for a single prompt and
+    /// tears it down afterward - reloads the whole model every call, so
+    /// it's only used when the worker pool couldn't be started.
+    async fn call_genie_one_shot(&self, prompt: &str) -> Result<String, String> {
         let genie_exe = self.genie_bundle_path.join("genie-t2t-run.exe");
         let config_file = self.genie_bundle_path.join("genie_config.json");
-        
-        info!("調用 Genie");
-        
+
+        info!("調用 Genie（one-shot fallback）");
+
         // Use tokio's spawn_blocking to execute synchronous commands
         let genie_exe_str = genie_exe.to_string_lossy().to_string();
         let config_file_str = config_file.to_string_lossy().to_string();
         let prompt_owned = prompt.to_owned();
-        let timeout_ms = self.timeout_ms;
-        
+
         let result = tokio::task::spawn_blocking(move || {
             // Execute Genie on Windows
             let output = Command::new(genie_exe_str)
@@ -159,18 +491,18 @@ impl GenieEngine {
                 .arg(config_file_str)
                 .arg("-p")
                 .arg(prompt_owned)
-                .current_dir("./genie_bundle") 
+                .current_dir("./genie_bundle")
                 .output()
                 .map_err(|e| format!("Failed to execute Genie: {}", e))?;
-            
+
             if !output.status.success() {
                 let error_msg = String::from_utf8_lossy(&output.stderr);
                 return Err(format!("Genie error: {}", error_msg));
             }
-            
+
             let response = String::from_utf8_lossy(&output.stdout);
-            
-            
+
+
             // Parse Genie output to extract AI response
             // Genie output format: [BEGIN]: <response> [END]
             if let Some(start) = response.find("[BEGIN]:") {
@@ -184,12 +516,12 @@ impl GenieEngine {
             } else {
                 Err("Genie response missing [BEGIN] marker".to_string())
             }
-            
+
             // If parsing fails, return raw response
             //info!("Genie return：{}", response);
             //Ok(response.to_string())
         }).await;
-        
+
         match result {
             Ok(Ok(response)) => {
                 info!("Genie success");
@@ -207,7 +539,7 @@ impl GenieEngine {
         } else {
             content.to_string()
         };
-        
+
         match basic_type {
             BasicContentType::Url => {
                 format!(
@@ -222,7 +554,7 @@ impl GenieEngine {
                 )
             },
             BasicContentType::PlainText => {
-                let language = if self.is_english(&truncated_content) { "English" } else { "Traditional Chinese" };
+                let language = self.detect_language(&truncated_content).display_name();
                 format!(
                     "<|begin_of_text|><|start_header_id|>user<|end_header_id|>\n\nAnalyze this {} text: {}\n\nSuggest 2-3 actions from:\n1.English Translate to Tradtional Chinese or Chinese Translate to English\n2. Summarize\n3. Extract keywords\n4. Analyze sentiment\n5. Search related\n6. Rewrite\n\nAnswer with action numbers only, separated by commas.<|eot_id|><|start_header_id|>assistant<|end_header_id|>",
                     language, truncated_content
@@ -241,14 +573,15 @@ impl GenieEngine {
     fn parse_ai_response(&self, response: &str, basic_type: &BasicContentType) -> Vec<AiActionSuggestion> {
         let mut suggestions = Vec::new();
         let response_lower = response.to_lowercase();
-        
+
         info!("Parsing AI response: {}", &response[..100.min(response.len())]);
-        
+        let _ = &response_lower;
+
         // Parse numbers from response
         let numbers: Vec<&str> = response.split(|c: char| c == ',' || c.is_whitespace())
             .filter(|s| !s.is_empty())
             .collect();
-        
+
         match basic_type {
             BasicContentType::Url => {
                 for num in &numbers {
@@ -289,12 +622,12 @@ impl GenieEngine {
             },
             _ => {}
         }
-        
+
         // If no valid suggestions, use fallback
         if suggestions.is_empty() {
             suggestions = self.get_fallback_suggestions(basic_type);
         }
-        
+
         suggestions
     }
 
@@ -304,10 +637,10 @@ impl GenieEngine {
             action_id: id.to_string(),
             label: label.to_string(),
             icon: icon.to_string(),
-            action_type: if id.starts_with("ai_") { 
-                AiActionType::AiProcessing 
-            } else { 
-                AiActionType::SystemAction 
+            action_type: if id.starts_with("ai_") {
+                AiActionType::AiProcessing
+            } else {
+                AiActionType::SystemAction
             },
             confidence,
             reason: Some(format!("Genie AI suggested: {}", label)),
@@ -332,14 +665,62 @@ impl GenieEngine {
         }
     }
 
-    /// Detect if text is primarily English
-    fn is_english(&self, text: &str) -> bool {
-        let english_chars = text.chars().filter(|c| c.is_ascii_alphabetic()).count();
-        let total_chars = text.chars().filter(|c| !c.is_whitespace()).count();
-        
-        if total_chars == 0 { return false; }
-        
-        (english_chars as f32 / total_chars as f32) > 0.7
+    /// Rough, dependency-free language guess from character script ranges
+    /// - good enough to pick a sane translation prompt/default target
+    /// without pulling in a statistical language-ID library.
+    fn detect_language(&self, text: &str) -> Lang {
+        let mut han = 0usize;
+        let mut kana = 0usize;
+        let mut hangul = 0usize;
+        let mut latin_diacritic = 0usize;
+        let mut ascii_alpha = 0usize;
+        let mut total = 0usize;
+
+        for c in text.chars() {
+            if c.is_whitespace() || c.is_ascii_punctuation() {
+                continue;
+            }
+            total += 1;
+            if ('\u{3040}'..='\u{30FF}').contains(&c) {
+                kana += 1;
+            } else if ('\u{AC00}'..='\u{D7A3}').contains(&c) {
+                hangul += 1;
+            } else if ('\u{4E00}'..='\u{9FFF}').contains(&c) {
+                han += 1;
+            } else if c.is_ascii_alphabetic() {
+                ascii_alpha += 1;
+            } else if c.is_alphabetic() {
+                latin_diacritic += 1;
+            }
+        }
+
+        if total == 0 {
+            return Lang::English;
+        }
+        let ratio = |n: usize| n as f32 / total as f32;
+
+        if ratio(kana) > 0.1 {
+            Lang::Japanese
+        } else if ratio(hangul) > 0.1 {
+            Lang::Korean
+        } else if ratio(han) > 0.1 {
+            // Without a traditional/simplified character table we can't
+            // reliably tell these apart; default to the more common case
+            // and let an explicit `target_lang` parameter override it.
+            Lang::TraditionalChinese
+        } else if ratio(latin_diacritic) > 0.05 {
+            if text.chars().any(|c| matches!(c, 'ü' | 'ß' | 'ä' | 'ö' | 'Ä' | 'Ö' | 'Ü')) {
+                Lang::German
+            } else if text.chars().any(|c| matches!(c, 'ñ' | 'Ñ' | '¿' | '¡')) {
+                Lang::Spanish
+            } else {
+                Lang::French
+            }
+        } else if ratio(ascii_alpha) > 0.5 {
+            Lang::English
+        } else {
+            Lang::Other
+        }
     }
 
     /// Full analysis function
@@ -350,7 +731,7 @@ impl GenieEngine {
         _rule_analysis: Option<&RuleAnalysis>,
     ) -> Result<AiAnalysis, String> {
         let intent_predictions = self.predict_intent(content, basic_type).await?;
-        
+
         let confidence = if !intent_predictions.is_empty() {
             intent_predictions.iter()
                 .map(|p| p.confidence)
@@ -358,7 +739,7 @@ impl GenieEngine {
         } else {
             0.0
         };
-        
+
         Ok(AiAnalysis {
             intent_predictions,
             summary: None,
@@ -366,4 +747,4 @@ impl GenieEngine {
             raw_response: None,
         })
     }
-}
\ No newline at end of file
+}
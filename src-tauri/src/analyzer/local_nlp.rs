@@ -0,0 +1,244 @@
+// src-tauri/src/analyzer/local_nlp.rs
+//! On-device NLP fallback used when Genie isn't available (no bundle on
+//! this machine, or the worker pool failed to start). Built on rust-bert's
+//! ready-to-use pipelines so `GenieEngine` can still return a real
+//! sentiment label, summary, or translation instead of a canned
+//! suggestion button.
+use crate::clipboard::types::{AiActionSuggestion, AiActionType, BasicContentType};
+use async_trait::async_trait;
+
+/// Minimal surface any on-device or remote NLP backend needs to provide so
+/// `GenieEngine::analyze`/`process_ai_task` can try Genie first and
+/// transparently fall through to it, without knowing which implementation
+/// actually answered.
+#[async_trait]
+pub trait AnalysisBackend: Send + Sync {
+    async fn predict_intent(
+        &self,
+        content: &str,
+        basic_type: &BasicContentType,
+    ) -> Result<Vec<AiActionSuggestion>, String>;
+
+    /// `target_lang` is a BCP-47-ish code (see `genie_engine::Lang::from_code`)
+    /// for the `"translate"` task; other task types ignore it.
+    async fn process_ai_task(&self, content: &str, task_type: &str, target_lang: Option<&str>) -> Result<String, String>;
+}
+
+/// Picks the best backend compiled in: the real rust-bert pipelines when
+/// the `rust_bert` feature is enabled, otherwise a stub that reports it's
+/// unavailable so callers keep using Genie's own static fallback list.
+pub fn default_backend() -> Box<dyn AnalysisBackend> {
+    #[cfg(feature = "rust_bert")]
+    {
+        Box::new(rust_bert_backend::RustBertBackend::new())
+    }
+    #[cfg(not(feature = "rust_bert"))]
+    {
+        Box::new(UnavailableBackend)
+    }
+}
+
+#[cfg(not(feature = "rust_bert"))]
+struct UnavailableBackend;
+
+#[cfg(not(feature = "rust_bert"))]
+#[async_trait]
+impl AnalysisBackend for UnavailableBackend {
+    async fn predict_intent(
+        &self,
+        _content: &str,
+        _basic_type: &BasicContentType,
+    ) -> Result<Vec<AiActionSuggestion>, String> {
+        Err("Local NLP backend not compiled in (enable the `rust_bert` feature)".to_string())
+    }
+
+    async fn process_ai_task(&self, _content: &str, _task_type: &str, _target_lang: Option<&str>) -> Result<String, String> {
+        Err("Local NLP backend not compiled in (enable the `rust_bert` feature)".to_string())
+    }
+}
+
+#[cfg(feature = "rust_bert")]
+mod rust_bert_backend {
+    use super::*;
+    use rust_bert::pipelines::keywords_extraction::KeywordExtractionModel;
+    use rust_bert::pipelines::sentiment::SentimentModel;
+    use rust_bert::pipelines::summarization::SummarizationModel;
+    use rust_bert::pipelines::translation::{Language, TranslationModel, TranslationModelBuilder};
+    use std::sync::{Mutex, OnceLock};
+
+    /// On-device fallback built on rust-bert's ready-to-use pipelines.
+    /// Each pipeline loads its weights lazily on first use rather than at
+    /// construction, so picking this backend doesn't pay for a
+    /// translation model just because the app asked for sentiment.
+    pub struct RustBertBackend {
+        sentiment: OnceLock<Mutex<SentimentModel>>,
+        summarization: OnceLock<Mutex<SummarizationModel>>,
+        translation: OnceLock<Mutex<TranslationModel>>,
+        keywords: OnceLock<Mutex<KeywordExtractionModel<'static>>>,
+    }
+
+    impl RustBertBackend {
+        pub fn new() -> Self {
+            Self {
+                sentiment: OnceLock::new(),
+                summarization: OnceLock::new(),
+                translation: OnceLock::new(),
+                keywords: OnceLock::new(),
+            }
+        }
+
+        fn sentiment_model(&self) -> Result<&Mutex<SentimentModel>, String> {
+            if self.sentiment.get().is_none() {
+                let model = SentimentModel::new(Default::default())
+                    .map_err(|e| format!("Failed to load rust-bert sentiment model: {}", e))?;
+                let _ = self.sentiment.set(Mutex::new(model));
+            }
+            Ok(self.sentiment.get().unwrap())
+        }
+
+        fn summarization_model(&self) -> Result<&Mutex<SummarizationModel>, String> {
+            if self.summarization.get().is_none() {
+                let model = SummarizationModel::new(Default::default())
+                    .map_err(|e| format!("Failed to load rust-bert summarization model: {}", e))?;
+                let _ = self.summarization.set(Mutex::new(model));
+            }
+            Ok(self.summarization.get().unwrap())
+        }
+
+        fn translation_model(&self) -> Result<&Mutex<TranslationModel>, String> {
+            if self.translation.get().is_none() {
+                let model = TranslationModelBuilder::new()
+                    .with_source_languages(vec![Language::English])
+                    .with_target_languages(vec![Language::ChineseMandarin])
+                    .create_model()
+                    .map_err(|e| format!("Failed to load rust-bert translation model: {}", e))?;
+                let _ = self.translation.set(Mutex::new(model));
+            }
+            Ok(self.translation.get().unwrap())
+        }
+
+        fn keywords_model(&self) -> Result<&Mutex<KeywordExtractionModel<'static>>, String> {
+            if self.keywords.get().is_none() {
+                let model = KeywordExtractionModel::new(Default::default())
+                    .map_err(|e| format!("Failed to load rust-bert keyword extraction model: {}", e))?;
+                let _ = self.keywords.set(Mutex::new(model));
+            }
+            Ok(self.keywords.get().unwrap())
+        }
+    }
+
+    #[async_trait]
+    impl AnalysisBackend for RustBertBackend {
+        async fn predict_intent(
+            &self,
+            content: &str,
+            _basic_type: &BasicContentType,
+        ) -> Result<Vec<AiActionSuggestion>, String> {
+            let sentiment = self.sentiment_model()?;
+            let content = content.to_string();
+
+            let (polarity, score) = tokio::task::block_in_place(|| {
+                let model = sentiment
+                    .lock()
+                    .map_err(|_| "rust-bert sentiment model mutex poisoned".to_string())?;
+                let output = model
+                    .predict(&[content.as_str()])
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| "rust-bert returned no sentiment output".to_string())?;
+                Ok::<_, String>((output.polarity, output.score as f32))
+            })?;
+
+            Ok(vec![AiActionSuggestion {
+                action_id: "ai_sentiment".to_string(),
+                label: format!("Sentiment: {:?} ({:.0}%)", polarity, score * 100.0),
+                icon: "😊".to_string(),
+                action_type: AiActionType::AiProcessing,
+                confidence: score,
+                reason: Some("rust-bert on-device sentiment analysis".to_string()),
+                parameters: None,
+            }])
+        }
+
+        async fn process_ai_task(&self, content: &str, task_type: &str, target_lang: Option<&str>) -> Result<String, String> {
+            let content = content.to_string();
+
+            match task_type {
+                "summarize" | "summarize_webpage" => {
+                    let model = self.summarization_model()?;
+                    tokio::task::block_in_place(|| {
+                        let model = model
+                            .lock()
+                            .map_err(|_| "rust-bert summarization model mutex poisoned".to_string())?;
+                        model
+                            .summarize(&[content.as_str()])
+                            .into_iter()
+                            .next()
+                            .ok_or_else(|| "rust-bert returned no summary".to_string())
+                    })
+                }
+                "translate" => {
+                    let model = self.translation_model()?;
+                    // Unrecognized/absent codes keep the original
+                    // English->Mandarin default rather than erroring, since
+                    // this backend only ever had that one language pair
+                    // before `target_lang` existed.
+                    let target = target_lang
+                        .and_then(parse_rust_bert_language)
+                        .unwrap_or(Language::ChineseMandarin);
+                    tokio::task::block_in_place(|| {
+                        let model = model
+                            .lock()
+                            .map_err(|_| "rust-bert translation model mutex poisoned".to_string())?;
+                        model
+                            .translate(&[content.as_str()], None, target)
+                            .map_err(|e| format!("rust-bert translation failed: {}", e))?
+                            .into_iter()
+                            .next()
+                            .ok_or_else(|| "rust-bert returned no translation".to_string())
+                    })
+                }
+                "extract_keywords" => {
+                    let model = self.keywords_model()?;
+                    tokio::task::block_in_place(|| {
+                        let model = model
+                            .lock()
+                            .map_err(|_| "rust-bert keyword extraction model mutex poisoned".to_string())?;
+                        let keywords = model
+                            .predict(&[content.as_str()])
+                            .map_err(|e| format!("rust-bert keyword extraction failed: {}", e))?;
+                        Ok(keywords
+                            .into_iter()
+                            .next()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|k| k.text)
+                            .collect::<Vec<_>>()
+                            .join(", "))
+                    })
+                }
+                other => Err(format!(
+                    "Local NLP backend has no pipeline for task type '{}'",
+                    other
+                )),
+            }
+        }
+    }
+
+    /// Maps a BCP-47-ish code (as produced by `genie_engine::Lang::code`)
+    /// to rust-bert's own `Language` token, so the on-device translation
+    /// pipeline honors the same `target_lang` the cloud/Genie paths do
+    /// instead of being stuck on a single hardcoded pair.
+    fn parse_rust_bert_language(code: &str) -> Option<Language> {
+        match code.to_ascii_lowercase().as_str() {
+            "en" => Some(Language::English),
+            "zh-hant" | "zh-hans" => Some(Language::ChineseMandarin),
+            "ja" => Some(Language::Japanese),
+            "ko" => Some(Language::Korean),
+            "fr" => Some(Language::French),
+            "es" => Some(Language::Spanish),
+            "de" => Some(Language::German),
+            _ => None,
+        }
+    }
+}
@@ -1,8 +1,22 @@
 // src-tauri/src/analyzer/mod.rs
 pub mod rule_engine;
+pub mod providers;
 pub mod ai_engine;
+// On-device fallback engine for when the cloud/HTTP `AiEngine` is
+// unreachable; only `ContentAnalyzer` talks to it, so it stays private
+// to this module instead of being part of the crate's public API.
+mod genie_engine;
+mod local_nlp;
 pub mod content_analyzer;
+pub mod redaction;
+pub mod bayes;
+pub mod url_rules;
+pub mod feedback;
 
 pub use rule_engine::RuleEngine;
+pub use providers::LanguageModelProvider;
 pub use ai_engine::AiEngine;
-pub use content_analyzer::ContentAnalyzer;
\ No newline at end of file
+pub use content_analyzer::ContentAnalyzer;
+pub use redaction::Redactor;
+pub use bayes::BayesClassifier;
+pub use feedback::ActionFeedbackStore;
\ No newline at end of file
@@ -0,0 +1,328 @@
+// src-tauri/src/analyzer/redaction.rs
+//! Masks sensitive substrings (credit cards, IBANs, SSNs, API-key-looking
+//! tokens) before content is handed to a remote AI provider or written to
+//! persistent history. `ClipboardEvent.content` itself is left untouched -
+//! callers that need the safe form read `RuleAnalysis::redacted_content`.
+
+use std::collections::HashSet;
+use std::env;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RedactionCategory {
+    CreditCard,
+    Iban,
+    Ssn,
+    ApiKey,
+}
+
+impl RedactionCategory {
+    fn label(&self) -> &'static str {
+        match self {
+            RedactionCategory::CreditCard => "credit_card",
+            RedactionCategory::Iban => "iban",
+            RedactionCategory::Ssn => "ssn",
+            RedactionCategory::ApiKey => "api_key",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "credit_card" => Some(RedactionCategory::CreditCard),
+            "iban" => Some(RedactionCategory::Iban),
+            "ssn" => Some(RedactionCategory::Ssn),
+            "api_key" => Some(RedactionCategory::ApiKey),
+            _ => None,
+        }
+    }
+
+    fn all() -> HashSet<RedactionCategory> {
+        [
+            RedactionCategory::CreditCard,
+            RedactionCategory::Iban,
+            RedactionCategory::Ssn,
+            RedactionCategory::ApiKey,
+        ]
+        .into_iter()
+        .collect()
+    }
+}
+
+pub struct RedactionResult {
+    /// `None` if nothing matched - the content can be used as-is.
+    pub redacted_content: Option<String>,
+    /// 0.0 (nothing found) to 1.0 (multiple categories found).
+    pub sensitivity: f32,
+    pub categories_found: Vec<&'static str>,
+}
+
+impl RedactionResult {
+    pub fn is_sensitive(&self) -> bool {
+        self.redacted_content.is_some()
+    }
+}
+
+/// Scans content for sensitive substrings and masks the ones in
+/// `enabled` categories. Stateless and cheap to construct per-scan.
+pub struct Redactor {
+    enabled: HashSet<RedactionCategory>,
+}
+
+impl Redactor {
+    pub fn new() -> Self {
+        Self {
+            enabled: Self::load_enabled_categories(),
+        }
+    }
+
+    /// All categories are redacted by default; set
+    /// `CLIPMIND_REDACT_CATEGORIES` to a comma-separated subset (e.g.
+    /// `credit_card,ssn`) to narrow it.
+    fn load_enabled_categories() -> HashSet<RedactionCategory> {
+        match env::var("CLIPMIND_REDACT_CATEGORIES") {
+            Ok(raw) => raw
+                .split(',')
+                .filter_map(|s| RedactionCategory::from_label(s.trim()))
+                .collect(),
+            Err(_) => RedactionCategory::all(),
+        }
+    }
+
+    pub fn scan(&self, content: &str) -> RedactionResult {
+        let mut redacted = content.to_string();
+        let mut categories_found = Vec::new();
+
+        if self.enabled.contains(&RedactionCategory::CreditCard) && mask_credit_cards(&mut redacted) {
+            categories_found.push(RedactionCategory::CreditCard.label());
+        }
+        if self.enabled.contains(&RedactionCategory::Iban) && mask_pattern(&mut redacted, &IBAN_REGEX, "[REDACTED_IBAN]") {
+            categories_found.push(RedactionCategory::Iban.label());
+        }
+        if self.enabled.contains(&RedactionCategory::Ssn) && mask_pattern(&mut redacted, &SSN_REGEX, "[REDACTED_SSN]") {
+            categories_found.push(RedactionCategory::Ssn.label());
+        }
+        if self.enabled.contains(&RedactionCategory::ApiKey) {
+            // `|` rather than `||` - both must run so a named-provider key
+            // elsewhere in the content isn't skipped just because the
+            // generic pass already masked something (or vice versa).
+            let named = mask_pattern(&mut redacted, &API_KEY_REGEX, "[REDACTED_API_KEY]");
+            let generic = mask_generic_secret(&mut redacted);
+            if named | generic {
+                categories_found.push(RedactionCategory::ApiKey.label());
+            }
+        }
+
+        let sensitivity = match categories_found.len() {
+            0 => 0.0,
+            1 => 0.6,
+            2 => 0.8,
+            _ => 1.0,
+        };
+
+        RedactionResult {
+            redacted_content: if categories_found.is_empty() { None } else { Some(redacted) },
+            sensitivity,
+            categories_found,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref IBAN_REGEX: regex::Regex = regex::Regex::new(
+        r"\b[A-Z]{2}\d{2}[A-Z0-9]{10,30}\b"
+    ).unwrap();
+
+    static ref SSN_REGEX: regex::Regex = regex::Regex::new(
+        r"\b\d{3}-\d{2}-\d{4}\b"
+    ).unwrap();
+
+    // Common vendor-prefixed secrets (sk-, ghp_, xox*, AIza...). A long
+    // opaque token with none of these prefixes is still checked, but via
+    // `mask_generic_secret` below - an unconstrained `{32,}` alternative
+    // here would match plain hex (UUIDs, commit SHAs) and kebab-case URL
+    // slugs just as readily as an actual key.
+    static ref API_KEY_REGEX: regex::Regex = regex::Regex::new(
+        r"\b(?:sk-[A-Za-z0-9]{20,}|ghp_[A-Za-z0-9]{30,}|xox[baprs]-[A-Za-z0-9-]{10,}|AIza[A-Za-z0-9_-]{30,})\b"
+    ).unwrap();
+
+    /// Candidate run for `mask_generic_secret` - `regex` has no lookahead,
+    /// so the character-mix/pure-hex checks that rule out UUIDs, SHAs, and
+    /// slugs happen in Rust after matching, the same way `mask_credit_cards`
+    /// validates candidates with Luhn instead of baking it into the regex.
+    static ref GENERIC_SECRET_CANDIDATE_REGEX: regex::Regex = regex::Regex::new(
+        r"\b[A-Za-z0-9_-]{32,}\b"
+    ).unwrap();
+}
+
+fn mask_pattern(content: &mut String, pattern: &regex::Regex, replacement: &str) -> bool {
+    if pattern.is_match(content) {
+        *content = pattern.replace_all(content, replacement).into_owned();
+        true
+    } else {
+        false
+    }
+}
+
+/// Finds 13-19 digit runs (allowing spaces/dashes as separators, the way
+/// cards are usually copied) and masks only the ones that pass the Luhn
+/// check, so a phone number or order id with the right digit count isn't
+/// mistaken for a card.
+fn mask_credit_cards(content: &mut String) -> bool {
+    lazy_static::lazy_static! {
+        static ref CARD_CANDIDATE_REGEX: regex::Regex = regex::Regex::new(
+            r"\b(?:\d[ -]?){12,18}\d\b"
+        ).unwrap();
+    }
+
+    let mut found = false;
+    let masked = CARD_CANDIDATE_REGEX.replace_all(&content.clone(), |caps: &regex::Captures| {
+        let candidate = &caps[0];
+        let digits: String = candidate.chars().filter(|c| c.is_ascii_digit()).collect();
+        if luhn_is_valid(&digits) {
+            found = true;
+            "[REDACTED_CARD]".to_string()
+        } else {
+            candidate.to_string()
+        }
+    });
+
+    if found {
+        *content = masked.into_owned();
+    }
+    found
+}
+
+/// Masks unprefixed 32+ char tokens that look like a high-entropy secret
+/// rather than a UUID, commit SHA, or URL slug - see
+/// `GENERIC_SECRET_CANDIDATE_REGEX`/`looks_like_generic_secret`.
+fn mask_generic_secret(content: &mut String) -> bool {
+    let mut found = false;
+    let masked = GENERIC_SECRET_CANDIDATE_REGEX.replace_all(&content.clone(), |caps: &regex::Captures| {
+        let candidate = &caps[0];
+        if looks_like_generic_secret(candidate) {
+            found = true;
+            "[REDACTED_API_KEY]".to_string()
+        } else {
+            candidate.to_string()
+        }
+    });
+
+    if found {
+        *content = masked.into_owned();
+    }
+    found
+}
+
+/// A real opaque secret is base62-ish and mixes at least two of
+/// upper/lower/digit; a UUID or commit SHA is plain hex (+ dashes) and a
+/// URL slug is usually all-lowercase words - this rejects those two
+/// common false-positive shapes without needing a real entropy estimate.
+fn looks_like_generic_secret(candidate: &str) -> bool {
+    let is_plain_hex = candidate.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    if is_plain_hex {
+        return false;
+    }
+
+    let has_upper = candidate.chars().any(|c| c.is_ascii_uppercase());
+    let has_lower = candidate.chars().any(|c| c.is_ascii_lowercase());
+    let has_digit = candidate.chars().any(|c| c.is_ascii_digit());
+
+    has_upper as u8 + has_lower as u8 + has_digit as u8 >= 2
+}
+
+fn luhn_is_valid(digits: &str) -> bool {
+    if digits.len() < 13 || digits.len() > 19 {
+        return false;
+    }
+
+    let mut sum = 0u32;
+    let mut double = false;
+    for c in digits.chars().rev() {
+        let mut d = match c.to_digit(10) {
+            Some(d) => d,
+            None => return false,
+        };
+        if double {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+        double = !double;
+    }
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_valid_credit_card() {
+        let redactor = Redactor::new();
+        // Well-known Luhn-valid test card number.
+        let result = redactor.scan("my card is 4111 1111 1111 1111, keep it safe");
+        assert!(result.is_sensitive());
+        assert!(result.redacted_content.unwrap().contains("[REDACTED_CARD]"));
+    }
+
+    #[test]
+    fn test_leaves_non_luhn_digit_run_alone() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("order number 1234567890123456");
+        assert!(!result.is_sensitive());
+    }
+
+    #[test]
+    fn test_masks_ssn() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("SSN: 123-45-6789");
+        assert!(result.is_sensitive());
+        assert!(result.redacted_content.unwrap().contains("[REDACTED_SSN]"));
+    }
+
+    #[test]
+    fn test_masks_api_key() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("key=sk-abcdefghijklmnopqrstuvwxyz123456");
+        assert!(result.is_sensitive());
+        assert!(result.redacted_content.unwrap().contains("[REDACTED_API_KEY]"));
+    }
+
+    #[test]
+    fn test_plain_text_is_untouched() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("just a normal clipboard note");
+        assert!(!result.is_sensitive());
+        assert_eq!(result.sensitivity, 0.0);
+    }
+
+    #[test]
+    fn test_masks_generic_mixed_case_secret() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("token: aZ3bQ9xR7mK2pL8vN4tY6wC1sF0dH5jE");
+        assert!(result.is_sensitive());
+        assert!(result.redacted_content.unwrap().contains("[REDACTED_API_KEY]"));
+    }
+
+    #[test]
+    fn test_leaves_uuid_alone() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("request id: 3fa85f64-5717-4562-b3fc-2c963f66afa6");
+        assert!(!result.is_sensitive());
+    }
+
+    #[test]
+    fn test_leaves_commit_sha_alone() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("fixed in a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0");
+        assert!(!result.is_sensitive());
+    }
+
+    #[test]
+    fn test_leaves_url_slug_alone() {
+        let redactor = Redactor::new();
+        let result = redactor.scan("https://example.com/blog/this-is-a-long-descriptive-slug-for-a-post");
+        assert!(!result.is_sensitive());
+    }
+}
@@ -1,16 +1,38 @@
 //src-tauri/src/analyzer/rule_engine.rs
+use crate::analyzer::bayes::{BayesClassifier, INCONCLUSIVE_MARGIN};
+use crate::analyzer::redaction::Redactor;
+use crate::analyzer::url_rules;
 use crate::clipboard::types::{BasicContentType, RuleAnalysis, ActionSuggestion};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
-pub struct RuleEngine;
+pub struct RuleEngine {
+    redactor: Redactor,
+    /// Learns `PlainText` categories from accepted suggestions (see
+    /// `train`), so repeat content ("another shopping list") can skip the
+    /// AI round trip once the classifier is confident. `Mutex` rather than
+    /// `RwLock` since `train` is rare relative to `analyze` and the table
+    /// is small enough that write contention isn't a concern.
+    bayes: Mutex<BayesClassifier>,
+}
 
 impl RuleEngine {
     pub fn new() -> Self {
-        Self
+        Self {
+            redactor: Redactor::new(),
+            bayes: Mutex::new(BayesClassifier::new()),
+        }
     }
-    
+
+    /// Records that the user accepted (or otherwise confirmed) `category`
+    /// for `text`, so future `analyze` calls on similar `PlainText` content
+    /// recognize it locally instead of needing the AI engine.
+    pub fn train(&self, text: &str, category: &str) {
+        self.bayes.lock().unwrap().train(text, category);
+    }
+
     pub fn analyze(&self, content: &str, basic_type: BasicContentType) -> RuleAnalysis {
-        match basic_type {
+        let mut analysis = match basic_type {
             BasicContentType::Url => self.analyze_url(content),
             BasicContentType::Email => self.analyze_email(content),
             BasicContentType::Phone => self.analyze_phone(content),
@@ -18,39 +40,105 @@ impl RuleEngine {
             BasicContentType::Code => self.analyze_code(content),
             BasicContentType::Address => self.analyze_address(content),
             BasicContentType::DateTime => self.analyze_datetime(content),
-            
-            // AI extension point: PlainText is fully handled by AI
-            BasicContentType::PlainText => RuleAnalysis {
-                confidence: 0.1,
-                metadata: HashMap::new(),
-                suggested_actions: vec![
-                    // Temporary skip: in the future, this will be AI-generated smart suggestions
-                    ActionSuggestion::immediate("save_text", "Save Text", "💾", "1"),
-                ],
-                needs_ai_analysis: true,  // Mark that AI is needed
-            },
-        }
+            BasicContentType::Image => self.analyze_image(),
+
+            BasicContentType::PlainText => self.analyze_plain_text(content),
+        };
+
+        // PII/secret detection runs regardless of content type - a credit
+        // card or API key can show up inside plain text just as easily as
+        // inside something already classified as Financial.
+        let redaction = self.redactor.scan(content);
+        analysis.redacted_content = redaction.redacted_content;
+        analysis.sensitivity = redaction.sensitivity;
+
+        analysis
     }
     
+    /// `PlainText` used to be fully punted to AI (confidence 0.1, always
+    /// `needs_ai_analysis`). Now it's first run past the locally-trained
+    /// `bayes` classifier: a confident category (see `INCONCLUSIVE_MARGIN`)
+    /// is returned as-is with no AI call, and only a genuinely inconclusive
+    /// score (untrained, or content unlike anything seen before) still
+    /// falls through to AI.
+    fn analyze_plain_text(&self, content: &str) -> RuleAnalysis {
+        let classified = self.bayes.lock().unwrap().classify(content);
+
+        if let Some((category, score)) = classified {
+            if (score - 0.5).abs() >= INCONCLUSIVE_MARGIN {
+                let mut metadata = HashMap::new();
+                metadata.insert("bayes_category".to_string(), category.clone());
+
+                return RuleAnalysis {
+                    confidence: score,
+                    metadata,
+                    suggested_actions: vec![
+                        ActionSuggestion::immediate("save_text", "Save Text", "💾", "1"),
+                    ],
+                    needs_ai_analysis: false,
+                    ..Default::default()
+                };
+            }
+        }
+
+        // AI extension point: inconclusive/untrained PlainText still goes to AI
+        RuleAnalysis {
+            confidence: 0.1,
+            metadata: HashMap::new(),
+            suggested_actions: vec![
+                // Temporary skip: in the future, this will be AI-generated smart suggestions
+                ActionSuggestion::immediate("save_text", "Save Text", "💾", "1"),
+            ],
+            needs_ai_analysis: true,  // Mark that AI is needed
+            ..Default::default()
+        }
+    }
+
     fn analyze_url(&self, content: &str) -> RuleAnalysis {
-        let domain = self.extract_domain(content);
-        
         let mut actions = vec![
             ActionSuggestion::immediate("open_browser", "Open Link", "🌐", "1"),
             ActionSuggestion::immediate("save_bookmark", "Save Bookmark", "⭐", "2"),
         ];
-        
+        let mut metadata = HashMap::new();
+        let mut next_hotkey = 3;
+
+        match url_rules::canonicalize(content) {
+            Some(cleaned) => {
+                metadata.insert("domain".to_string(), cleaned.registrable_domain);
+                metadata.insert("clean_url".to_string(), cleaned.clean_url.clone());
+
+                actions.push(ActionSuggestion::immediate(
+                    "clean_url",
+                    "Copy Clean Link",
+                    "🧹",
+                    &next_hotkey.to_string(),
+                ));
+                next_hotkey += 1;
+
+                // Domain-specific actions (e.g. GitHub -> Clone Repo) on top
+                // of the generic open/bookmark/clean-link actions above.
+                for (id, label, icon) in url_rules::domain_actions(&cleaned.host) {
+                    actions.push(ActionSuggestion::immediate(id, label, icon, &next_hotkey.to_string()));
+                    next_hotkey += 1;
+                }
+            }
+            None => {
+                // `ContentDetector`'s URL regex is looser than `url::Url`'s
+                // parser - fall back to the old naive split for whatever
+                // slipped through without a recognizable host.
+                metadata.insert("domain".to_string(), self.fallback_domain(content));
+            }
+        }
+
         // AI extension point: in the future, AI can enhance this with smart suggestions
         // Example: AI analyzes webpage content and provides personalized suggestions
-        
-        let mut metadata = HashMap::new();
-        metadata.insert("domain".to_string(), domain);
-        
+
         RuleAnalysis {
             confidence: 0.95,
             metadata,
             suggested_actions: actions,
             needs_ai_analysis: true,  // URL can also be enhanced by AI
+            ..Default::default()
         }
     }
     
@@ -65,6 +153,7 @@ impl RuleEngine {
             metadata: HashMap::new(),
             suggested_actions: actions,
             needs_ai_analysis: false,  // Email does not need AI enhancement
+            ..Default::default()
         }
     }
     
@@ -81,6 +170,7 @@ impl RuleEngine {
             metadata: HashMap::new(),
             suggested_actions: actions,
             needs_ai_analysis: false,
+            ..Default::default()
         }
     }
     
@@ -103,6 +193,7 @@ impl RuleEngine {
             metadata,
             suggested_actions: actions,
             needs_ai_analysis: false,
+            ..Default::default()
         }
     }
     
@@ -120,6 +211,7 @@ impl RuleEngine {
             metadata,
             suggested_actions: actions,
             needs_ai_analysis: true,  // Code can be enhanced by AI
+            ..Default::default()
         }
     }
     
@@ -134,6 +226,7 @@ impl RuleEngine {
             metadata: HashMap::new(),
             suggested_actions: actions,
             needs_ai_analysis: false,
+            ..Default::default()
         }
     }
     
@@ -148,10 +241,32 @@ impl RuleEngine {
             metadata: HashMap::new(),
             suggested_actions: actions,
             needs_ai_analysis: false,
+            ..Default::default()
         }
     }
     
-    fn extract_domain(&self, url: &str) -> String {
+    /// `content` here is just the `"[image WxH, N bytes]"` preview string
+    /// (see `ClipboardPayload::text_preview`), so there's nothing for the
+    /// rule heuristics to read - every image always needs the AI engine's
+    /// OCR/vision task to say anything more specific than "save it".
+    fn analyze_image(&self) -> RuleAnalysis {
+        let actions = vec![
+            ActionSuggestion::immediate("save_image", "Save Image", "💾", "1"),
+            ActionSuggestion::immediate("copy_image", "Copy Image", "📋", "2"),
+        ];
+
+        RuleAnalysis {
+            confidence: 0.5,
+            metadata: HashMap::new(),
+            suggested_actions: actions,
+            needs_ai_analysis: true,
+            ..Default::default()
+        }
+    }
+
+    /// Naive last-resort domain extraction for URL-shaped content
+    /// `url_rules::canonicalize` couldn't parse at all.
+    fn fallback_domain(&self, url: &str) -> String {
         url.split('/')
             .nth(2)
             .unwrap_or("unknown")
@@ -0,0 +1,221 @@
+// src-tauri/src/analyzer/url_rules.rs
+//! Typed URL canonicalization and host-based action routing for
+//! `RuleEngine::analyze_url`.
+//!
+//! Replaces the old `url.split('/').nth(2)` domain extraction, which broke
+//! on userinfo (`user:pass@host`), non-default ports, and query strings.
+//! `canonicalize` parses with `url::Url` instead, strips known tracking
+//! query parameters and AMP markers, and reports both the cleaned URL and
+//! its (approximate) registrable domain. `domain_actions` then looks the
+//! parsed host up in a small table of known domains to add
+//! destination-specific actions on top of the generic open/bookmark ones.
+
+use url::Url;
+
+/// Query parameter name prefixes stripped during canonicalization - these
+/// only ever carry analytics/tracking data, never anything the destination
+/// page needs to render.
+const TRACKING_PARAM_PREFIXES: &[&str] = &["utm_"];
+
+/// Exact (not prefix) query parameter names stripped for the same reason,
+/// plus `amp` itself (Google's AMP redirect marker, e.g. `?amp=1`).
+const TRACKING_PARAM_EXACT: &[&str] = &["fbclid", "gclid", "msclkid", "amp"];
+
+/// Path segments that mark an AMP page mirroring a canonical one
+/// (`example.com/amp/article` -> `example.com/article`); stripped rather
+/// than left in `clean_url`.
+const AMP_PATH_SEGMENTS: &[&str] = &["amp"];
+
+/// A cleaned URL's components, see `canonicalize`.
+pub struct CleanedUrl {
+    /// The de-tracked, AMP-collapsed URL, suitable for the `clean_url` action.
+    pub clean_url: String,
+    /// Lowercased host, used for `domain_actions` lookups.
+    pub host: String,
+    /// Approximate registrable domain (e.g. `github.com`, `bbc.co.uk`) -
+    /// see `registrable_domain`'s doc comment for the heuristic used.
+    pub registrable_domain: String,
+}
+
+/// One known host's domain-specific `(action_id, label, icon)` triples.
+struct DomainRule {
+    host_suffix: &'static str,
+    actions: &'static [(&'static str, &'static str, &'static str)],
+}
+
+/// Known domains to route extra actions for, beyond the generic open/
+/// bookmark/clean-link ones every URL gets. Matched by `matches_host`
+/// (exact or dot-suffix), never substring, so e.g. `evil-github.com`
+/// cannot spoof `github.com`.
+const DOMAIN_RULES: &[DomainRule] = &[
+    DomainRule {
+        host_suffix: "github.com",
+        actions: &[
+            ("clone_repo", "Clone Repo", "📦"),
+            ("open_ide", "Open in IDE", "💻"),
+        ],
+    },
+    DomainRule {
+        host_suffix: "youtube.com",
+        actions: &[("open_player", "Open in Player", "▶️")],
+    },
+    DomainRule {
+        host_suffix: "youtu.be",
+        actions: &[("open_player", "Open in Player", "▶️")],
+    },
+    DomainRule {
+        host_suffix: "maps.google.com",
+        actions: &[("open_maps", "Open in Maps", "🗺️")],
+    },
+    DomainRule {
+        host_suffix: "maps.apple.com",
+        actions: &[("open_maps", "Open in Maps", "🗺️")],
+    },
+];
+
+/// Parses `raw` as a URL (assuming `https://` if it has no scheme, since
+/// `ContentDetector`'s URL regex accepts bare domains) and returns its
+/// cleaned form, or `None` if it isn't parseable as a URL at all.
+pub fn canonicalize(raw: &str) -> Option<CleanedUrl> {
+    let trimmed = raw.trim();
+    let mut url = Url::parse(trimmed)
+        .or_else(|_| Url::parse(&format!("https://{}", trimmed)))
+        .ok()?;
+
+    let host = url.host_str()?.to_lowercase();
+    url.set_host(Some(&host)).ok()?;
+
+    strip_tracking_params(&mut url);
+    collapse_amp_path(&mut url);
+
+    Some(CleanedUrl {
+        clean_url: url.to_string(),
+        registrable_domain: registrable_domain(&host),
+        host,
+    })
+}
+
+/// Domain-specific actions for `host` (exact or dot-suffix match against
+/// `DOMAIN_RULES`), empty if nothing matches.
+pub fn domain_actions(host: &str) -> &'static [(&'static str, &'static str, &'static str)] {
+    DOMAIN_RULES
+        .iter()
+        .find(|rule| matches_host(host, rule.host_suffix))
+        .map(|rule| rule.actions)
+        .unwrap_or(&[])
+}
+
+/// True if `host` is exactly `suffix` or a subdomain of it (`www.github.com`
+/// matches `github.com`; `evil-github.com` does not - there's no `.` right
+/// before `github.com` in it).
+fn matches_host(host: &str, suffix: &str) -> bool {
+    host == suffix || host.ends_with(&format!(".{}", suffix))
+}
+
+/// Rebuilds `url`'s query string with every `utm_*`/`fbclid`/`gclid`/
+/// `msclkid`/`amp` parameter removed, or drops the query entirely if
+/// nothing is left.
+fn strip_tracking_params(url: &mut Url) {
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(&kept);
+    }
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let lower = key.to_lowercase();
+    TRACKING_PARAM_EXACT.contains(&lower.as_str())
+        || TRACKING_PARAM_PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+}
+
+/// Strips a leading/trailing `amp` path segment (`/amp/article`,
+/// `/article/amp`) so an AMP mirror collapses back to its canonical path.
+fn collapse_amp_path(url: &mut Url) {
+    let Some(segments) = url.path_segments() else { return };
+    let filtered: Vec<String> = segments
+        .filter(|segment| !AMP_PATH_SEGMENTS.contains(&segment.to_lowercase().as_str()))
+        .map(str::to_string)
+        .collect();
+
+    if let Ok(mut path_mut) = url.path_segments_mut() {
+        path_mut.clear();
+        for segment in &filtered {
+            path_mut.push(segment);
+        }
+    }
+}
+
+/// Approximates the registrable domain (the part a user would actually
+/// register, e.g. `github.com` out of `gist.github.com`) by taking the
+/// last two labels, or the last three when the second-to-last label looks
+/// like a compound ccTLD component (`co`, `com`, `org`, `net`, `gov`, `ac`
+/// immediately before a two-letter country code, e.g. `bbc.co.uk`). This
+/// is a heuristic, not a real Public Suffix List lookup - good enough for
+/// display/dedup purposes, not for security decisions.
+fn registrable_domain(host: &str) -> String {
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() <= 2 {
+        return host.to_string();
+    }
+
+    let second_last = labels[labels.len() - 2];
+    let last = labels[labels.len() - 1];
+    let looks_like_compound_cctld = last.len() == 2
+        && matches!(second_last, "co" | "com" | "org" | "net" | "gov" | "ac" | "edu");
+
+    let take = if looks_like_compound_cctld { 3 } else { 2 };
+    labels[labels.len().saturating_sub(take)..].join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_utm_and_click_id_params() {
+        let cleaned = canonicalize(
+            "https://example.com/article?utm_source=x&utm_medium=y&fbclid=abc&id=42",
+        )
+        .unwrap();
+        assert_eq!(cleaned.clean_url, "https://example.com/article?id=42");
+    }
+
+    #[test]
+    fn collapses_amp_path_segment() {
+        let cleaned = canonicalize("https://example.com/amp/article").unwrap();
+        assert_eq!(cleaned.clean_url, "https://example.com/article");
+    }
+
+    #[test]
+    fn lowercases_host() {
+        let cleaned = canonicalize("https://EXAMPLE.com/Path").unwrap();
+        assert_eq!(cleaned.host, "example.com");
+    }
+
+    #[test]
+    fn registrable_domain_handles_compound_cctld() {
+        assert_eq!(registrable_domain("www.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(registrable_domain("gist.github.com"), "github.com");
+        assert_eq!(registrable_domain("github.com"), "github.com");
+    }
+
+    #[test]
+    fn domain_actions_use_suffix_not_substring_match() {
+        assert!(domain_actions("evil-github.com").is_empty());
+        assert!(!domain_actions("github.com").is_empty());
+        assert!(!domain_actions("gist.github.com").is_empty());
+    }
+
+    #[test]
+    fn bare_domain_without_scheme_parses() {
+        let cleaned = canonicalize("github.com/rust-lang/rust").unwrap();
+        assert_eq!(cleaned.host, "github.com");
+    }
+}
@@ -0,0 +1,95 @@
+// src-tauri/src/clipboard/backend/macos.rs
+//! macOS backend: Cocoa has no clipboard-change notification API, so
+//! `NSPasteboard.changeCount` is polled on a short interval instead. This
+//! is the documented Apple-recommended way to detect pasteboard changes
+//! and is cheap enough (a single integer read) to poll frequently.
+
+use super::{BackendHandle, ClipboardBackend, WorkerPulseTx};
+use crate::clipboard::types::{ClipboardError, ClipboardSelection};
+
+use cocoa::appkit::NSPasteboard;
+use cocoa::base::nil;
+use log::{debug, info};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// How often to poll `changeCount`. Short enough to feel instantaneous,
+/// long enough not to waste CPU.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub struct MacosBackend;
+
+impl MacosBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ClipboardBackend for MacosBackend {
+    // macOS has a single general pasteboard, so `selections` (PRIMARY/SECONDARY
+    // are an X11 concept) is ignored here.
+    fn start(
+        &self,
+        pulse_tx: WorkerPulseTx,
+        _selections: &[ClipboardSelection],
+    ) -> Result<Box<dyn BackendHandle>, ClipboardError> {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+
+        let handle = std::thread::spawn(move || {
+            run_poll_loop(pulse_tx, thread_running);
+        });
+
+        Ok(Box::new(MacosHandle {
+            running,
+            loop_thread: Some(handle),
+        }))
+    }
+}
+
+fn run_poll_loop(pulse_tx: WorkerPulseTx, running: Arc<AtomicBool>) {
+    let mut last_change_count = unsafe { current_change_count() };
+    info!("macOS NSPasteboard.changeCount polling started (every {:?})", POLL_INTERVAL);
+
+    while running.load(Ordering::SeqCst) {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let change_count = unsafe { current_change_count() };
+        if change_count != last_change_count {
+            last_change_count = change_count;
+            if let Err(e) = pulse_tx.send(ClipboardSelection::Clipboard) {
+                debug!("Worker pulse send failed (likely stopping): {}", e);
+                break;
+            }
+        }
+    }
+}
+
+unsafe fn current_change_count() -> i64 {
+    let pasteboard = NSPasteboard::generalPasteboard(nil);
+    pasteboard.changeCount()
+}
+
+struct MacosHandle {
+    running: Arc<AtomicBool>,
+    loop_thread: Option<JoinHandle<()>>,
+}
+
+impl BackendHandle for MacosHandle {
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.loop_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MacosHandle {
+    fn drop(&mut self) {
+        if self.loop_thread.is_some() {
+            self.stop();
+        }
+    }
+}
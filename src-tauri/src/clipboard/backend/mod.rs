@@ -0,0 +1,80 @@
+// src-tauri/src/clipboard/backend/mod.rs
+//! Platform-specific clipboard change notification.
+//!
+//! Every OS has a different way of telling us "the clipboard changed" (a
+//! Win32 message, an X11 selection-owner event, a Wayland data-device
+//! event, or nothing at all on macOS). `ClipboardBackend` isolates that
+//! one concern; everything else (debounce, retry, dedup, reading the
+//! actual content) stays in `run_worker` in `monitor.rs` and is shared
+//! across platforms.
+
+use super::types::{ClipboardError, ClipboardSelection};
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(all(unix, not(target_os = "macos")))]
+mod x11;
+#[cfg(all(unix, not(target_os = "macos")))]
+mod wayland;
+#[cfg(target_os = "macos")]
+mod macos;
+
+#[cfg(target_os = "windows")]
+pub use windows::WindowsBackend;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub use x11::X11Backend;
+#[cfg(all(unix, not(target_os = "macos")))]
+pub use wayland::WaylandBackend;
+#[cfg(target_os = "macos")]
+pub use macos::MacosBackend;
+
+/// Sending end of the "something changed, go re-read the clipboard" pulse.
+/// Kept as a plain `std::sync::mpsc` sender so blocking worker threads can
+/// receive from it without pulling in an async runtime. Carries which
+/// selection changed, so the worker can keep per-selection dedup state;
+/// backends with only one clipboard (Windows, macOS) always send
+/// `ClipboardSelection::Clipboard`.
+pub type WorkerPulseTx = std::sync::mpsc::Sender<ClipboardSelection>;
+
+/// A running backend. Dropping or calling `stop()` must make the backend's
+/// background thread(s) exit promptly; `start_monitoring`/`stop_monitoring`
+/// behave identically regardless of which concrete backend is behind this.
+pub trait ClipboardBackend: Send + Sync {
+    /// Start listening for clipboard-change notifications, sending a pulse
+    /// on `pulse_tx` every time the backend observes (or suspects) a
+    /// change. `selections` lists which selections the caller wants
+    /// watched; backends that only know about one clipboard (Windows,
+    /// macOS) ignore it. Reading the actual clipboard content happens in
+    /// the shared worker, not here.
+    fn start(
+        &self,
+        pulse_tx: WorkerPulseTx,
+        selections: &[ClipboardSelection],
+    ) -> Result<Box<dyn BackendHandle>, ClipboardError>;
+}
+
+/// Handle returned by `ClipboardBackend::start`, used to tear the backend
+/// down. `stop()` must be safe to call from any thread.
+pub trait BackendHandle: Send {
+    fn stop(&mut self);
+}
+
+/// Construct the backend appropriate for the platform we're compiled for.
+/// This is the only `#[cfg(target_os = ...)]` switch callers need.
+pub fn platform_backend() -> Box<dyn ClipboardBackend> {
+    #[cfg(target_os = "windows")]
+    {
+        Box::new(WindowsBackend::new())
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        match std::env::var("WAYLAND_DISPLAY") {
+            Ok(_) => Box::new(WaylandBackend::new()),
+            Err(_) => Box::new(X11Backend::new()),
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Box::new(MacosBackend::new())
+    }
+}
@@ -0,0 +1,130 @@
+// src-tauri/src/clipboard/backend/wayland.rs
+//! Wayland backend: a dedicated event-queue thread drives the
+//! `wl_data_device` protocol and pulses the worker every time the
+//! compositor delivers a `selection` event (i.e. the clipboard owner
+//! changed), which is the Wayland analogue of Win32's
+//! `WM_CLIPBOARDUPDATE` / X11's XFIXES selection-owner notification.
+
+use super::{BackendHandle, ClipboardBackend, WorkerPulseTx};
+use crate::clipboard::types::{ClipboardError, ClipboardSelection};
+
+use log::{debug, error, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use wayland_client::protocol::{wl_data_device, wl_data_device_manager, wl_seat};
+use wayland_client::{Connection, Dispatch, QueueHandle};
+
+pub struct WaylandBackend;
+
+impl WaylandBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ClipboardBackend for WaylandBackend {
+    fn start(
+        &self,
+        pulse_tx: WorkerPulseTx,
+        selections: &[ClipboardSelection],
+    ) -> Result<Box<dyn BackendHandle>, ClipboardError> {
+        // wl_data_device only exposes the regular clipboard selection;
+        // PRIMARY/SECONDARY would need the separate zwp_primary_selection
+        // protocol, which isn't wired up here yet.
+        if selections
+            .iter()
+            .any(|s| *s != ClipboardSelection::Clipboard)
+        {
+            warn!(
+                "Wayland backend only watches the CLIPBOARD selection; PRIMARY/SECONDARY were requested but are not supported"
+            );
+        }
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+
+        let handle = std::thread::spawn(move || {
+            if let Err(e) = run_event_loop(pulse_tx, thread_running) {
+                error!("Wayland clipboard monitoring failed: {}", e);
+            }
+        });
+
+        Ok(Box::new(WaylandHandle {
+            running,
+            loop_thread: Some(handle),
+        }))
+    }
+}
+
+struct SelectionWatcher {
+    pulse_tx: WorkerPulseTx,
+}
+
+impl Dispatch<wl_data_device::WlDataDevice, ()> for SelectionWatcher {
+    fn event(
+        state: &mut Self,
+        _proxy: &wl_data_device::WlDataDevice,
+        event: wl_data_device::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        if let wl_data_device::Event::Selection { .. } = event {
+            if let Err(e) = state.pulse_tx.send(ClipboardSelection::Clipboard) {
+                debug!("Worker pulse send failed (likely stopping): {}", e);
+            }
+        }
+    }
+}
+
+wayland_client::delegate_noop!(SelectionWatcher: ignore wl_seat::WlSeat);
+wayland_client::delegate_noop!(SelectionWatcher: ignore wl_data_device_manager::WlDataDeviceManager);
+
+fn run_event_loop(
+    pulse_tx: WorkerPulseTx,
+    running: Arc<AtomicBool>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let conn = Connection::connect_to_env()?;
+    let (globals, mut queue) = wayland_client::globals::registry_queue_init::<SelectionWatcher>(&conn)?;
+    let qh = queue.handle();
+
+    let seat: wl_seat::WlSeat = globals.bind(&qh, 1..=9, ())?;
+    let manager: wl_data_device_manager::WlDataDeviceManager = globals.bind(&qh, 1..=3, ())?;
+    let _data_device = manager.get_data_device(&seat, &qh, ());
+
+    let mut state = SelectionWatcher { pulse_tx };
+
+    info!("Wayland data-device selection watch registered");
+
+    while running.load(Ordering::SeqCst) {
+        queue.dispatch_pending(&mut state)?;
+        conn.flush()?;
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    Ok(())
+}
+
+struct WaylandHandle {
+    running: Arc<AtomicBool>,
+    loop_thread: Option<JoinHandle<()>>,
+}
+
+impl BackendHandle for WaylandHandle {
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.loop_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for WaylandHandle {
+    fn drop(&mut self) {
+        if self.loop_thread.is_some() {
+            self.stop();
+        }
+    }
+}
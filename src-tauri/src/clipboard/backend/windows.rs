@@ -0,0 +1,227 @@
+// src-tauri/src/clipboard/backend/windows.rs
+//! Win32 backend: an invisible message-only-ish window registered as a
+//! clipboard format listener (`AddClipboardFormatListener`). Every
+//! `WM_CLIPBOARDUPDATE` message becomes one pulse to the shared worker.
+//!
+//! Shutdown is the one tricky part: `DestroyWindow` must run on the thread
+//! that owns the window (the message-loop thread), not on whichever thread
+//! calls `stop()`. So `stop()` just posts a `WM_CLIPBOARDUPDATE` with a
+//! reserved sentinel `lparam`; the window procedure recognizes it and tears
+//! itself down from the right thread, which also wakes the blocked
+//! `GetMessageW` loop immediately instead of waiting for the next real
+//! clipboard event.
+
+use super::{BackendHandle, ClipboardBackend, WorkerPulseTx};
+use crate::clipboard::types::{ClipboardError, ClipboardSelection};
+
+use log::{debug, error, info};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use windows::Win32::{
+    Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+    System::LibraryLoader::GetModuleHandleW,
+    UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW,
+        GetWindowLongPtrW, PostMessageW, PostQuitMessage, RegisterClassExW, SetWindowLongPtrW,
+        TranslateMessage, UnregisterClassW, CREATESTRUCTW, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT,
+        GWLP_USERDATA, MSG, WM_CLIPBOARDUPDATE, WM_CREATE, WM_DESTROY, WNDCLASSEXW,
+    },
+};
+
+extern "system" {
+    fn AddClipboardFormatListener(hwnd: HWND) -> i32;
+    fn RemoveClipboardFormatListener(hwnd: HWND) -> i32;
+}
+
+/// Reserved `lparam` value that can never arrive from a real
+/// `WM_CLIPBOARDUPDATE` (which Windows always sends with `lparam == 0`);
+/// used to tell the window procedure "this is the shutdown request".
+const SHUTDOWN_SENTINEL: isize = -1;
+
+struct WindowContext {
+    worker_tx: Option<WorkerPulseTx>,
+}
+
+pub struct WindowsBackend;
+
+impl WindowsBackend {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn run_message_loop(context: Arc<Mutex<WindowContext>>) -> Result<(), Box<dyn std::error::Error>> {
+        unsafe {
+            let class_name = windows::core::w!("ClipMindMonitor");
+            let hinstance: HINSTANCE = GetModuleHandleW(None)?.into();
+
+            let _ = UnregisterClassW(class_name, hinstance);
+
+            let wc = WNDCLASSEXW {
+                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+                style: CS_HREDRAW | CS_VREDRAW,
+                lpfnWndProc: Some(Self::window_proc),
+                cbClsExtra: 0,
+                cbWndExtra: 0,
+                hInstance: hinstance,
+                hIcon: Default::default(),
+                hCursor: Default::default(),
+                hbrBackground: Default::default(),
+                lpszMenuName: windows::core::PCWSTR::null(),
+                lpszClassName: class_name,
+                hIconSm: Default::default(),
+            };
+
+            if RegisterClassExW(&wc) == 0 {
+                return Err("Register window class failed".into());
+            }
+
+            // The context is handed to the window via lpParam (WM_CREATE's
+            // CREATESTRUCT::lpCreateParams) and stashed in GWLP_USERDATA, so
+            // the window procedure can reach it without any global state.
+            let context_ptr = Arc::into_raw(context) as *const std::ffi::c_void;
+
+            let hwnd = CreateWindowExW(
+                Default::default(),
+                class_name,
+                windows::core::w!("ClipMind Clipboard Monitor"),
+                Default::default(),
+                CW_USEDEFAULT,
+                CW_USEDEFAULT,
+                CW_USEDEFAULT,
+                CW_USEDEFAULT,
+                None,
+                None,
+                hinstance,
+                Some(context_ptr),
+            );
+
+            if hwnd.0 == 0 {
+                // CreateWindowExW failed before WM_CREATE reached our
+                // handler, so the Arc was never reclaimed there - reclaim it here.
+                drop(Arc::from_raw(context_ptr as *const Mutex<WindowContext>));
+                return Err("Create window failed".into());
+            }
+
+            if AddClipboardFormatListener(hwnd) == 0 {
+                DestroyWindow(hwnd);
+                return Err("Register clipboard listener failed".into());
+            }
+
+            info!("Windows clipboard listener registered, hwnd: {:?}", hwnd);
+
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            info!("Windows message loop ended");
+            let _ = UnregisterClassW(class_name, hinstance);
+            Ok(())
+        }
+    }
+
+    unsafe extern "system" fn window_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match msg {
+            WM_CREATE => {
+                let create_struct = lparam.0 as *const CREATESTRUCTW;
+                SetWindowLongPtrW(hwnd, GWLP_USERDATA, (*create_struct).lpCreateParams as isize);
+                LRESULT(0)
+            }
+            WM_CLIPBOARDUPDATE if lparam.0 == SHUTDOWN_SENTINEL => {
+                info!("Shutdown sentinel received, tearing down on owning thread");
+                RemoveClipboardFormatListener(hwnd);
+                // Reclaim and drop the context Arc we took ownership of in WM_CREATE.
+                let context_ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const Mutex<WindowContext>;
+                if !context_ptr.is_null() {
+                    drop(Arc::from_raw(context_ptr));
+                    SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0);
+                }
+                DestroyWindow(hwnd);
+                LRESULT(0)
+            }
+            WM_CLIPBOARDUPDATE => {
+                let context_ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const Mutex<WindowContext>;
+                if !context_ptr.is_null() {
+                    // Borrow without taking ownership: wrap in ManuallyDrop so
+                    // this temporary Arc doesn't decrement the refcount on drop.
+                    let context = std::mem::ManuallyDrop::new(Arc::from_raw(context_ptr));
+                    if let Ok(ctx) = context.lock() {
+                        if let Some(tx) = &ctx.worker_tx {
+                            if let Err(e) = tx.send(ClipboardSelection::Clipboard) {
+                                debug!("Worker pulse send failed (likely stopping): {}", e);
+                            }
+                        }
+                    }
+                }
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                info!("Window destroyed");
+                PostQuitMessage(0);
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+}
+
+impl ClipboardBackend for WindowsBackend {
+    // Windows has a single system clipboard, so `selections` (PRIMARY/SECONDARY
+    // are an X11 concept) is ignored here.
+    fn start(
+        &self,
+        pulse_tx: WorkerPulseTx,
+        _selections: &[ClipboardSelection],
+    ) -> Result<Box<dyn BackendHandle>, ClipboardError> {
+        let context = Arc::new(Mutex::new(WindowContext {
+            worker_tx: Some(pulse_tx),
+        }));
+
+        let handle = std::thread::spawn(move || {
+            if let Err(e) = Self::run_message_loop(context) {
+                error!("Windows clipboard monitoring failed: {}", e);
+            }
+        });
+
+        Ok(Box::new(WindowsHandle {
+            message_loop: Some(handle),
+        }))
+    }
+}
+
+struct WindowsHandle {
+    message_loop: Option<JoinHandle<()>>,
+}
+
+impl BackendHandle for WindowsHandle {
+    fn stop(&mut self) {
+        unsafe {
+            let class_name = windows::core::w!("ClipMindMonitor");
+            let hwnd = windows::Win32::UI::WindowsAndMessaging::FindWindowW(class_name, None);
+            if hwnd.0 != 0 {
+                let _ = PostMessageW(hwnd, WM_CLIPBOARDUPDATE, WPARAM(0), LPARAM(SHUTDOWN_SENTINEL));
+            } else {
+                debug!("Window already gone at stop() time; nothing to post to");
+            }
+        }
+
+        if let Some(handle) = self.message_loop.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for WindowsHandle {
+    fn drop(&mut self) {
+        if self.message_loop.is_some() {
+            self.stop();
+        }
+    }
+}
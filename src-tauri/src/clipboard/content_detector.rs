@@ -1,5 +1,97 @@
 // src-tauri/src/clipboard/content_detector.rs
-use super::types::{BasicContentType, ClipboardEvent};
+use super::types::{BasicContentType, ClipboardEvent, ClipboardPayload, ClipboardSelection};
+
+/// Margin `ContentAnalyzer` uses to decide whether the top two
+/// `detect_ranked` scores are "close enough" that the runner-up is worth
+/// surfacing to the AI engine as a secondary candidate.
+pub const CLOSE_SCORE_MARGIN: f32 = 0.15;
+
+/// Scalar features pulled from `content` in a single pass, then shared by
+/// every type's scorer below. Replaces the seven separate regex/keyword
+/// scans that used to each walk the string on their own.
+struct ContentFeatures {
+    digit_count: usize,
+    total_chars: usize,
+    newline_count: usize,
+    paren_count: usize,
+    has_colon: bool,
+    has_currency_symbol: bool,
+    only_phone_chars: bool,
+    code_keyword_hits: usize,
+    sql_keyword_hits: usize,
+    address_keyword_hits: usize,
+}
+
+const CODE_KEYWORDS: [&str; 15] = [
+    "def ", "function ", "class ", "import ", "#include", "console.log",
+    "println", "system.out", "cout <<", "<?php", "#!/", "<script>",
+    "public class", "private ", "void ",
+];
+const SQL_KEYWORDS: [&str; 3] = ["select ", "from ", "where "];
+const ADDRESS_KEYWORDS: [&str; 10] = [
+    "街", "路", "巷", "弄", "號", "樓", "室", "市", "縣", "段",
+];
+
+impl ContentFeatures {
+    /// Walks `content` once, tallying the counts every scorer needs instead
+    /// of re-scanning the string per content type.
+    fn scan(content: &str) -> Self {
+        let lower = content.to_lowercase();
+
+        let mut digit_count = 0;
+        let mut total_chars = 0;
+        let mut newline_count = 0;
+        let mut paren_count = 0;
+        let mut has_colon = false;
+        let mut has_currency_symbol = false;
+        let mut only_phone_chars = true;
+
+        for c in content.chars() {
+            total_chars += 1;
+            if c.is_ascii_digit() {
+                digit_count += 1;
+            }
+            if c == '\n' {
+                newline_count += 1;
+            }
+            if c == '(' || c == ')' {
+                paren_count += 1;
+            }
+            if c == ':' {
+                has_colon = true;
+            }
+            if is_currency_symbol(c) {
+                has_currency_symbol = true;
+            }
+            if only_phone_chars
+                && !(c.is_ascii_digit() || c == '+' || c == '-' || c == ' ' || c == '(' || c == ')')
+            {
+                only_phone_chars = false;
+            }
+        }
+
+        Self {
+            digit_count,
+            total_chars: total_chars.max(1),
+            newline_count,
+            paren_count,
+            has_colon,
+            has_currency_symbol,
+            only_phone_chars,
+            code_keyword_hits: CODE_KEYWORDS.iter().filter(|kw| lower.contains(*kw)).count(),
+            sql_keyword_hits: SQL_KEYWORDS.iter().filter(|kw| lower.contains(*kw)).count(),
+            address_keyword_hits: ADDRESS_KEYWORDS.iter().filter(|kw| content.contains(*kw)).count(),
+        }
+    }
+
+    fn paren_ratio(&self) -> f64 {
+        self.paren_count as f64 / self.total_chars as f64
+    }
+}
+
+fn is_currency_symbol(c: char) -> bool {
+    matches!(c, '$' | '€' | '£' | '¥' | '₩' | '₹')
+}
 
 #[derive(Clone)]
 pub struct ContentDetector;
@@ -8,79 +100,127 @@ impl ContentDetector {
     pub fn new() -> Self {
         Self
     }
-    
-    pub fn detect(&self, content: &str) -> BasicContentType {
+
+    /// Runs every detector over `content` and returns each `BasicContentType`
+    /// with its confidence score, highest first. Unlike the old first-match
+    /// `detect`, ambiguous content (a phone number that also reads as a
+    /// date, a URL containing a financial amount) keeps both candidates
+    /// instead of silently committing to whichever detector ran first.
+    pub fn detect_ranked(&self, content: &str) -> Vec<(BasicContentType, f32)> {
         let content = content.trim();
-        
-        if self.is_url(content) {
-            return BasicContentType::Url;
-        }
-        
-        if self.is_email(content) {
-            return BasicContentType::Email;
-        }
-        
-        if self.is_financial(content) {
-            return BasicContentType::Financial;
-        }
-        
-        if self.is_datetime(content) {
-            return BasicContentType::DateTime;
-        }
+        let features = ContentFeatures::scan(content);
 
-        if self.is_phone(content) {
-            return BasicContentType::Phone;
-        }
+        let mut ranked = vec![
+            (BasicContentType::Url, self.score_url(content)),
+            (BasicContentType::Email, self.score_email(content)),
+            (BasicContentType::Financial, self.score_financial(content, &features)),
+            (BasicContentType::DateTime, self.score_datetime(content)),
+            (BasicContentType::Phone, self.score_phone(content, &features)),
+            (BasicContentType::Code, self.score_code(&features)),
+            (BasicContentType::Address, self.score_address(&features)),
+            (BasicContentType::PlainText, 0.05),
+        ];
 
-        if self.is_code(content) {
-            return BasicContentType::Code;
-        }
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
 
-        if self.is_address(content) {
-            return BasicContentType::Address;
-        }
-        
-        BasicContentType::PlainText
+    /// Thin backward-compat wrapper over `detect_ranked`: keeps returning a
+    /// single `BasicContentType` for callers that only care about the top
+    /// match (clipboard event tagging, the UI preview, etc).
+    pub fn detect(&self, content: &str) -> BasicContentType {
+        self.detect_ranked(content)
+            .into_iter()
+            .next()
+            .map(|(content_type, _)| content_type)
+            .unwrap_or(BasicContentType::PlainText)
     }
-    
+
     pub fn create_event(&self, content: String, source_app: Option<String>) -> ClipboardEvent {
         let content_type = self.detect(&content);
         ClipboardEvent::new(content, content_type, source_app)
     }
-    
-    fn is_url(&self, content: &str) -> bool {
+
+    /// Same as `create_event`, but for a payload that may not be text
+    /// (image, files). `Image` is tagged directly rather than run through
+    /// the text heuristics below, which would otherwise score the
+    /// `"[image WxH, N bytes]"` preview string as `PlainText`; `Files` still
+    /// falls back to text detection over its preview since a path list
+    /// doesn't have its own `BasicContentType`.
+    pub fn create_event_from_payload(
+        &self,
+        payload: ClipboardPayload,
+        source_app: Option<String>,
+    ) -> ClipboardEvent {
+        let content_type = self.detect_payload(&payload);
+        ClipboardEvent::from_payload(payload, content_type, source_app)
+    }
+
+    /// Same as `create_event_from_payload`, but tags the resulting event
+    /// with which selection (CLIPBOARD/PRIMARY/SECONDARY) it was read from.
+    pub fn create_event_for_selection(
+        &self,
+        payload: ClipboardPayload,
+        source_app: Option<String>,
+        selection: ClipboardSelection,
+    ) -> ClipboardEvent {
+        let content_type = self.detect_payload(&payload);
+        ClipboardEvent::from_payload_for_selection(payload, content_type, source_app, selection)
+    }
+
+    /// Shared by `create_event_from_payload`/`create_event_for_selection`.
+    fn detect_payload(&self, payload: &ClipboardPayload) -> BasicContentType {
+        match payload {
+            ClipboardPayload::Image { .. } => BasicContentType::Image,
+            _ => self.detect(&payload.text_preview()),
+        }
+    }
+
+    fn score_url(&self, content: &str) -> f32 {
         lazy_static::lazy_static! {
             static ref URL_REGEX: regex::Regex = regex::Regex::new(
                 r"^(https?://|ftp://)?([a-zA-Z0-9.-]+\.[a-zA-Z]{2,})(:[0-9]+)?(/.*)?$"
             ).unwrap();
+            static ref SCHEME_REGEX: regex::Regex = regex::Regex::new(r"^(https?|ftp)://").unwrap();
+        }
+
+        if !URL_REGEX.is_match(content) {
+            return 0.0;
+        }
+
+        if SCHEME_REGEX.is_match(content) {
+            0.95
+        } else {
+            0.75
         }
-        URL_REGEX.is_match(content)
     }
-    
-    fn is_email(&self, content: &str) -> bool {
+
+    fn score_email(&self, content: &str) -> f32 {
         lazy_static::lazy_static! {
             static ref EMAIL_REGEX: regex::Regex = regex::Regex::new(
                 r"^[^\s@]+@[^\s@]+\.[^\s@]+$"
             ).unwrap();
         }
-        EMAIL_REGEX.is_match(content)
+        if EMAIL_REGEX.is_match(content) {
+            0.9
+        } else {
+            0.0
+        }
     }
 
-    fn is_phone(&self, content: &str) -> bool {
-        let digits = content.chars().filter(|c| c.is_ascii_digit()).count();
-        if digits < 7 || digits > 15 {
-            return false;
+    fn score_phone(&self, content: &str, features: &ContentFeatures) -> f32 {
+        if features.digit_count < 7 || features.digit_count > 15 {
+            return 0.0;
         }
-
-        if content.contains(':') {
-            return false;
+        if features.has_colon || !features.only_phone_chars {
+            return 0.0;
         }
 
-        let has_phone_chars = content.chars().all(|c| c.is_ascii_digit() || c == '+' || c == '-' || c == ' ' || c == '(' || c == ')');
-        has_phone_chars
+        // More digits (up to a normal phone-number length) -> more confident.
+        0.6 + 0.3 * (features.digit_count.min(11) as f32 / 11.0)
     }
-    
-    fn is_financial(&self, content: &str) -> bool {
+
+    fn score_financial(&self, content: &str, features: &ContentFeatures) -> f32 {
         lazy_static::lazy_static! {
             static ref SYMBOL_REGEX: regex::Regex = regex::Regex::new(
                 r"(?ix) ^\s*(?:\p{Sc})\s*\d{1,3}(?:[,\d]{0,12})(?:[.]\d{1,2})?\s*$"
@@ -92,10 +232,21 @@ impl ContentDetector {
             ).unwrap();
         }
 
-        SYMBOL_REGEX.is_match(content) || CODE_REGEX.is_match(content)
+        if SYMBOL_REGEX.is_match(content) || CODE_REGEX.is_match(content) {
+            return 0.88;
+        }
+
+        // Inconclusive middle ground: a currency symbol plus digits, but not
+        // shaped strictly enough to be a confident exact match (e.g. it has
+        // trailing words around the amount).
+        if features.has_currency_symbol && features.digit_count > 0 {
+            0.3
+        } else {
+            0.0
+        }
     }
-    
-    fn is_datetime(&self, content: &str) -> bool {
+
+    fn score_datetime(&self, content: &str) -> f32 {
         lazy_static::lazy_static! {
             static ref DATE_REGEX: regex::Regex = regex::Regex::new(
                 r"(?x)
@@ -114,44 +265,34 @@ impl ContentDetector {
                 )$"
             ).unwrap();
         }
-        DATE_REGEX.is_match(content)
+        if DATE_REGEX.is_match(content) {
+            0.8
+        } else {
+            0.0
+        }
     }
-    
-    fn is_code(&self, content: &str) -> bool {
-        let code_keywords = [
-            "def ", "function ", "class ", "import ", "#include", "console.log",
-            "println", "System.out", "cout <<", "<?php", "#!/", "<script>", 
-            "public class", "private ", "void "
-        ];
-        let sql_keywords = ["select ", "from ", "where "];
-
-        let lower = content.to_lowercase();
-        let has_keywords = code_keywords.iter().any(|&kw| lower.contains(kw));
-        let has_sql = sql_keywords.iter().any(|&kw| lower.contains(kw));
-        let multiple_lines = content.lines().count() >= 5;
-        let total_chars = content.chars().count().max(1);
-        let paren_count = content.chars().filter(|&c| c == '(' || c == ')').count();
-        let paren_ratio = paren_count as f64 / total_chars as f64;
-
-        has_keywords || has_sql || (multiple_lines && (paren_ratio > 0.02))
-    }
-    
-    fn is_address(&self, content: &str) -> bool {
-        let address_keywords = [
-            "街", "路", "巷", "弄", "號", "樓", "室", "市", "縣", "段",
-        ];
 
-        let count = address_keywords
-            .iter()
-            .filter(|kw| content.contains(*kw))
-            .count();
+    fn score_code(&self, features: &ContentFeatures) -> f32 {
+        let multiple_lines = features.newline_count + 1 >= 5;
+        let paren_heavy = features.paren_ratio() > 0.02;
 
-        count >= 2
+        if features.code_keyword_hits > 0 || features.sql_keyword_hits > 0 {
+            0.8
+        } else if multiple_lines && paren_heavy {
+            0.6
+        } else {
+            0.0
+        }
     }
 
+    fn score_address(&self, features: &ContentFeatures) -> f32 {
+        if features.address_keyword_hits < 2 {
+            return 0.0;
+        }
+        0.6 + 0.1 * (features.address_keyword_hits.min(4) as f32 - 2.0)
+    }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,7 +300,7 @@ mod tests {
     #[test]
     fn test_url_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("https://github.com/microsoft/vscode"), BasicContentType::Url);
         assert_eq!(detector.detect("http://example.com"), BasicContentType::Url);
         assert_eq!(detector.detect("ftp://files.example.com"), BasicContentType::Url);
@@ -169,7 +310,7 @@ mod tests {
     #[test]
     fn test_email_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("test@example.com"), BasicContentType::Email);
         assert_eq!(detector.detect("user.name@domain.org"), BasicContentType::Email);
         assert_eq!(detector.detect("not an email"), BasicContentType::PlainText);
@@ -179,7 +320,7 @@ mod tests {
     #[test]
     fn test_phone_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("+886912345678"), BasicContentType::Phone);
         assert_eq!(detector.detect("0912-345-678"), BasicContentType::Phone);
         assert_eq!(detector.detect("(02) 1234-5678"), BasicContentType::Phone);
@@ -189,7 +330,7 @@ mod tests {
     #[test]
     fn test_code_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("def hello():\n    print('Hello')"), BasicContentType::Code);
         assert_eq!(detector.detect("function test() { return 42; }"), BasicContentType::Code);
         assert_eq!(detector.detect("#include <stdio.h>"), BasicContentType::Code);
@@ -200,7 +341,7 @@ mod tests {
     #[test]
     fn test_financial_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("$100"), BasicContentType::Financial);
         assert_eq!(detector.detect("NT$1000"), BasicContentType::Financial);
         assert_eq!(detector.detect("€50"), BasicContentType::Financial);
@@ -211,7 +352,7 @@ mod tests {
     #[test]
     fn test_datetime_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("2024-01-15"), BasicContentType::DateTime);
         assert_eq!(detector.detect("01/15/2024"), BasicContentType::DateTime);
         assert_eq!(detector.detect("14:30"), BasicContentType::DateTime);
@@ -221,7 +362,7 @@ mod tests {
     #[test]
     fn test_address_detection() {
         let detector = ContentDetector::new();
-        
+
         assert_eq!(detector.detect("台北市信義區信義路五段7號"), BasicContentType::Address);
         assert_eq!(detector.detect("123 Main Street, New York"), BasicContentType::Address);
         assert_eq!(detector.detect("short st"), BasicContentType::PlainText); // 太短
@@ -230,13 +371,35 @@ mod tests {
     #[test]
     fn test_create_event() {
         let detector = ContentDetector::new();
-        
+
         let event = detector.create_event("https://example.com".to_string(), Some("browser".to_string()));
-        
+
         assert_eq!(event.content_type, BasicContentType::Url);
         assert_eq!(event.content, "https://example.com");
         assert_eq!(event.source_app, Some("browser".to_string()));
         assert!(event.content_length > 0);
         assert!(!event.content_hash.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_detect_ranked_is_sorted_descending() {
+        let detector = ContentDetector::new();
+
+        let ranked = detector.detect_ranked("https://github.com/microsoft/vscode");
+        assert_eq!(ranked.first().unwrap().0, BasicContentType::Url);
+        for pair in ranked.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+    }
+
+    #[test]
+    fn test_detect_ranked_surfaces_ambiguous_runner_up() {
+        let detector = ContentDetector::new();
+
+        // A phone-shaped string that also looks like a date/time run should
+        // keep both candidates instead of committing to the first match.
+        let ranked = detector.detect_ranked("14:30:00");
+        let datetime_score = ranked.iter().find(|(t, _)| *t == BasicContentType::DateTime).unwrap().1;
+        assert!(datetime_score > 0.0);
+    }
+}
@@ -2,7 +2,9 @@
 pub mod types;
 pub mod monitor;
 pub mod content_detector;
+pub mod backend;
+pub mod sync;
 
 pub use types::*;
-pub use monitor::{ClipboardMonitor, ClipboardChange, MonitorConfig};
+pub use monitor::{ClipboardMonitor, ClipboardChange, MonitorConfig, AcceptedFormat, HandlerFilter, HandlerId};
 pub use content_detector::ContentDetector;
\ No newline at end of file
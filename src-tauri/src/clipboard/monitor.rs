@@ -1,33 +1,68 @@
 // src-tauri/src/clipboard/monitor.rs
-use super::types::{ClipboardEvent, ClipboardError};
+use super::types::{BasicContentType, ClipboardEvent, ClipboardError, ClipboardPayload, ClipboardSelection};
 use super::content_detector::ContentDetector;
+use super::backend::{self, BackendHandle};
 
 use arboard::Clipboard;
 use log::{debug, info, warn, error};
 use serde::{Serialize, Deserialize};
 
-use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::thread::JoinHandle;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::Instant;
 
-// Windows API 
-use windows::Win32::{
-    Foundation::{HWND, LPARAM, LRESULT, WPARAM, HINSTANCE},
-    System::LibraryLoader::GetModuleHandleW,
-    UI::WindowsAndMessaging::{
-        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW,
-        RegisterClassExW, UnregisterClassW, TranslateMessage, PostQuitMessage, DestroyWindow,
-        MSG, WNDCLASSEXW, WM_CLIPBOARDUPDATE, WM_DESTROY,
-        CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT,
-    },
-};
-
-// AddClipboardFormatListener and RemoveClipboardFormatListener
-extern "system" {
-    fn AddClipboardFormatListener(hwnd: HWND) -> i32;
-    fn RemoveClipboardFormatListener(hwnd: HWND) -> i32;
+/// Identifies a registered handler so it can later be removed.
+pub type HandlerId = u64;
+
+/// What kinds of clipboard changes a handler wants to see, so it doesn't
+/// have to re-implement its own filtering over the raw broadcast stream.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerFilter {
+    /// Only dispatch changes whose detected type is in this list.
+    /// `None` means "any content type".
+    pub content_types: Option<Vec<BasicContentType>>,
+    /// Only dispatch changes whose content is at least this long.
+    pub min_length: Option<usize>,
+}
+
+impl HandlerFilter {
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    pub fn for_types(content_types: Vec<BasicContentType>) -> Self {
+        Self {
+            content_types: Some(content_types),
+            min_length: None,
+        }
+    }
+
+    fn matches(&self, change: &ClipboardChange) -> bool {
+        if let Some(types) = &self.content_types {
+            if !types.contains(&change.event.content_type) {
+                return false;
+            }
+        }
+        if let Some(min_length) = self.min_length {
+            if change.event.content_length < min_length {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+type HandlerCallback = Arc<dyn Fn(&ClipboardChange) + Send + Sync>;
+type HandlerMap = Arc<std::sync::Mutex<HashMap<HandlerId, (HandlerFilter, HandlerCallback)>>>;
+
+/// Non-text clipboard formats callers can opt into. Text is always captured;
+/// these are additive so existing callers who only want text see no change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AcceptedFormat {
+    Image,
+    Files,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +77,14 @@ pub struct MonitorConfig {
     pub retry_max: u32,
     /// Initial retry delay (milliseconds), will exponentially back off up to 200ms
     pub retry_initial_delay_ms: u64,
+    /// Non-text formats to also capture when the clipboard holds no text
+    /// (e.g. a copied screenshot). Empty by default: text-only behavior.
+    pub accepted_formats: Vec<AcceptedFormat>,
+    /// Which X11/Wayland selections to watch. Defaults to just `Clipboard`
+    /// (the regular copy/paste clipboard); adding `Primary`/`Secondary`
+    /// also watches highlight-to-copy selections on platforms that have
+    /// them. Ignored on Windows/macOS, which only have one clipboard.
+    pub selections: Vec<ClipboardSelection>,
 }
 
 impl Default for MonitorConfig {
@@ -54,6 +97,8 @@ impl Default for MonitorConfig {
             debounce_ms: 60,
             retry_max: 8,
             retry_initial_delay_ms: 10,
+            accepted_formats: Vec::new(),
+            selections: vec![ClipboardSelection::Clipboard],
         }
     }
 }
@@ -70,24 +115,8 @@ enum MonitorCommand {
     Stop,
 }
 
-// Signal for the dedicated worker (only needs Pulse)
-type WorkerPulseTx = std::sync::mpsc::Sender<()>;
-type WorkerPulseRx = std::sync::mpsc::Receiver<()>;
-
-/// Shared context for the Win32 message loop thread (used by the window procedure)
-struct WindowsMonitorContext {
-    event_sender: broadcast::Sender<ClipboardChange>,
-    content_detector: ContentDetector,
-    config: MonitorConfig,
-    window_handle: HWND,
-    // Channel to send messages to the worker (only sends () to indicate updates)
-    worker_tx: Option<WorkerPulseTx>,
-}
-
-// ===== Global State =====
-static mut MESSAGE_LOOP_HANDLE: Option<JoinHandle<()>> = None;
-static MESSAGE_LOOP_RUNNING: AtomicBool = AtomicBool::new(false);
-static mut GLOBAL_CONTEXT: Option<Arc<Mutex<WindowsMonitorContext>>> = None;
+// Signal for the dedicated worker: which selection changed.
+type WorkerPulseRx = std::sync::mpsc::Receiver<ClipboardSelection>;
 
 // ===== Main External Object =====
 pub struct ClipboardMonitor {
@@ -101,6 +130,15 @@ pub struct ClipboardMonitor {
     // Control channel
     control_sender: Option<mpsc::UnboundedSender<MonitorCommand>>,
 
+    // Handle to the platform backend's background thread(s), used to tear it down on stop.
+    // Shared with the control-channel task below, which is what actually calls `stop()`.
+    backend_handle: Arc<std::sync::Mutex<Option<Box<dyn BackendHandle>>>>,
+
+    // Registered handlers, fanned out to by a dispatcher task started in `new()`.
+    // Kept alongside (not instead of) the broadcast channel for backward compatibility.
+    handlers: HandlerMap,
+    next_handler_id: Arc<AtomicU64>,
+
     // Basic state
     start_time: Option<Instant>,
     is_running: bool,
@@ -113,18 +151,64 @@ impl ClipboardMonitor {
         // Do not initialize Clipboard here; it will be exclusively owned by the worker thread
         let (event_sender, event_receiver) = broadcast::channel(1000);
 
+        let handlers: HandlerMap = Arc::new(std::sync::Mutex::new(HashMap::new()));
+
+        // The dispatcher runs independent of start/stop_monitoring so handlers
+        // registered before the monitor is started still see events once it is.
+        let dispatcher_handlers = Arc::clone(&handlers);
+        let mut dispatcher_rx = event_sender.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match dispatcher_rx.recv().await {
+                    Ok(change) => {
+                        let handlers = dispatcher_handlers.lock().unwrap();
+                        for (filter, callback) in handlers.values() {
+                            if filter.matches(&change) {
+                                callback(&change);
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        warn!("Handler dispatcher lagged, skipped {} events", count);
+                        continue;
+                    }
+                }
+            }
+        });
+
         Ok(Self {
             config,
             content_detector: ContentDetector::new(),
             event_sender,
             _event_receiver: event_receiver,
             control_sender: None,
+            backend_handle: Arc::new(std::sync::Mutex::new(None)),
+            handlers,
+            next_handler_id: Arc::new(AtomicU64::new(1)),
             start_time: None,
             is_running: false,
         })
     }
 
-        /// Start monitoring - Windows event-driven + dedicated worker
+    /// Register a handler that is called for every clipboard change
+    /// matching `filter`. Unlike subscribing to the raw broadcast channel,
+    /// callers don't have to re-implement their own content-type/length
+    /// filtering.
+    pub fn add_handler(&mut self, filter: HandlerFilter, cb: HandlerCallback) -> HandlerId {
+        let id = self.next_handler_id.fetch_add(1, Ordering::SeqCst);
+        self.handlers.lock().unwrap().insert(id, (filter, cb));
+        id
+    }
+
+    /// Unregister a previously-added handler. No-op if it's already gone.
+    pub fn remove_handler(&mut self, id: HandlerId) {
+        self.handlers.lock().unwrap().remove(&id);
+    }
+
+    /// Start monitoring - platform event-driven backend + dedicated worker.
+    /// Behaves identically on every OS: the backend just tells the worker
+    /// "go look", and the worker owns all reading/debounce/retry/dedup logic.
     pub async fn start_monitoring(&mut self) -> std::result::Result<broadcast::Receiver<ClipboardChange>, ClipboardError> {
         if self.is_running {
             return Err(ClipboardError::AccessError("Monitor is already running".to_string()));
@@ -139,7 +223,7 @@ impl ClipboardMonitor {
         let event_receiver = self.event_sender.subscribe();
 
         // Create worker channel (std mpsc, convenient for blocking recv in std::thread)
-        let (worker_tx, worker_rx): (WorkerPulseTx, WorkerPulseRx) = std::sync::mpsc::channel();
+        let (worker_tx, worker_rx) = std::sync::mpsc::channel();
 
         // Start worker thread (exclusive ownership of Clipboard)
         let worker_cfg = self.config.clone();
@@ -153,186 +237,32 @@ impl ClipboardMonitor {
             }
         });
 
-        // Set global context for the Win32 window thread
-        unsafe {
-            GLOBAL_CONTEXT = Some(Arc::new(Mutex::new(WindowsMonitorContext {
-                event_sender: self.event_sender.clone(),
-                content_detector: self.content_detector.clone(),
-                config: self.config.clone(),
-                window_handle: HWND(0),
-                worker_tx: Some(worker_tx),
-            })));
-        }
+        // Start the platform backend; it only pulses the worker on change
+        let platform_backend = backend::platform_backend();
+        let backend_handle = platform_backend.start(worker_tx, &self.config.selections)?;
+        *self.backend_handle.lock().unwrap() = Some(backend_handle);
 
-        // Start the Win32 message loop thread
-        unsafe {
-            MESSAGE_LOOP_RUNNING.store(true, Ordering::SeqCst);
-            MESSAGE_LOOP_HANDLE = Some(std::thread::spawn(move || {
-                if let Err(e) = Self::run_windows_message_loop() {
-                    error!("Windows clipboard monitoring failed: {}", e);
-                }
-                MESSAGE_LOOP_RUNNING.store(false, Ordering::SeqCst);
-            }));
-        }
-
-        // Control channel (stop)
+        let handle_for_stop = Arc::clone(&self.backend_handle);
         tokio::spawn(async move {
             while let Some(cmd) = control_rx.recv().await {
                 match cmd {
                     MonitorCommand::Stop => {
                         info!("Received stop command, ending monitoring");
-                        Self::stop_windows_monitoring();
+                        if let Ok(mut guard) = handle_for_stop.lock() {
+                            if let Some(mut handle) = guard.take() {
+                                handle.stop();
+                            }
+                        }
                         break;
                     }
                 }
             }
         });
 
-        info!("Windows API clipboard monitoring started");
+        info!("Clipboard monitoring started");
         Ok(event_receiver)
     }
 
-    fn run_windows_message_loop() -> Result<(), Box<dyn std::error::Error>> {
-        unsafe {
-            let class_name = windows::core::w!("ClipMindMonitor");
-            let hinstance: HINSTANCE = GetModuleHandleW(None)?.into();
-
-            // Safety: Attempt to unregister any potentially old class first
-            let _ = UnregisterClassW(class_name, hinstance);
-
-            let wc = WNDCLASSEXW {
-                cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
-                style: CS_HREDRAW | CS_VREDRAW,
-                lpfnWndProc: Some(Self::window_proc),
-                cbClsExtra: 0,
-                cbWndExtra: 0,
-                hInstance: hinstance,
-                hIcon: Default::default(),
-                hCursor: Default::default(),
-                hbrBackground: Default::default(),
-                lpszMenuName: windows::core::PCWSTR::null(),
-                lpszClassName: class_name,
-                hIconSm: Default::default(),
-            };
-
-            if RegisterClassExW(&wc) == 0 {
-                return Err("Register window class failed".into());
-            }
-
-            // Invisible window
-            let hwnd = CreateWindowExW(
-                Default::default(),
-                class_name,
-                windows::core::w!("ClipMind Clipboard Monitor"),
-                Default::default(),
-                CW_USEDEFAULT,
-                CW_USEDEFAULT,
-                CW_USEDEFAULT,
-                CW_USEDEFAULT,
-                None,
-                None,
-                hinstance,
-                None,
-            );
-
-            if hwnd.0 == 0 {
-                return Err("Create window failed".into());
-            }
-
-            // Record the hwnd
-            if let Some(context_arc) = &GLOBAL_CONTEXT {
-                if let Ok(mut context) = context_arc.lock() {
-                    context.window_handle = hwnd;
-                }
-            }
-
-            // Register clipboard listener
-            if AddClipboardFormatListener(hwnd) == 0 {
-                DestroyWindow(hwnd);
-                return Err("Register clipboard listener failed".into());
-            }
-
-            info!("Windows clipboard listener registered, hwnd: {:?}", hwnd);
-
-            // Message loop
-            let mut msg = MSG::default();
-            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
-                if !MESSAGE_LOOP_RUNNING.load(Ordering::SeqCst) {
-                    break;
-                }
-                TranslateMessage(&msg);
-                DispatchMessageW(&msg);
-            }
-
-            info!("Windows message loop ended");
-            let _ = UnregisterClassW(class_name, hinstance);
-            Ok(())
-        }
-    }
-
-    unsafe extern "system" fn window_proc(
-        hwnd: HWND,
-        msg: u32,
-        _wparam: WPARAM,
-        _lparam: LPARAM,
-    ) -> LRESULT {
-        match msg {
-            WM_CLIPBOARDUPDATE => {
-                // Only send a pulse to the worker, do not read the clipboard in this thread
-                if let Some(ctx_arc) = &GLOBAL_CONTEXT {
-                    if let Ok(ctx) = ctx_arc.lock() {
-                        if let Some(tx) = &ctx.worker_tx {
-                            // If the worker is busy or the channel is full, losing one or two pulses here is fine;
-                            // the worker has debouncing to merge events
-                            if let Err(e) = tx.send(()) {
-                                debug!("Worker pulse send failed (likely stopping): {}", e);
-                            }
-                        }
-                    }
-                }
-                LRESULT(0)
-            }
-            WM_DESTROY => {
-                info!("Window destroyed, removing clipboard listener");
-                RemoveClipboardFormatListener(hwnd);
-                PostQuitMessage(0);
-                LRESULT(0)
-            }
-            _ => DefWindowProcW(hwnd, msg, _wparam, _lparam),
-        }
-    }
-
-    fn stop_windows_monitoring() {
-        unsafe {
-            MESSAGE_LOOP_RUNNING.store(false, Ordering::SeqCst);
-
-            // First destroy the window (this will trigger WM_DESTROY -> PostQuitMessage)
-            if let Some(context_arc) = &GLOBAL_CONTEXT {
-                // Extract worker_tx and let it drop (this closes the channel, causing the worker to exit)
-                let mut maybe_worker_tx: Option<WorkerPulseTx> = None;
-
-                if let Ok(mut context) = context_arc.lock() {
-                    let hwnd = context.window_handle;
-                    if hwnd.0 != 0 {
-                        DestroyWindow(hwnd);
-                    } else {
-                        PostQuitMessage(0);
-                    }
-                    // Drop worker_tx, the worker loop will exit after recv() returns Err
-                    maybe_worker_tx = context.worker_tx.take();
-                    drop(maybe_worker_tx);
-                }
-
-                GLOBAL_CONTEXT = None;
-            }
-
-            // Wait for the message loop thread to finish
-            if let Some(handle) = MESSAGE_LOOP_HANDLE.take() {
-                let _ = handle.join();
-            }
-        }
-    }
-
     /// Stop monitoring - sync version
     pub fn stop_monitoring_sync(&mut self) -> std::result::Result<(), ClipboardError> {
         if !self.is_running {
@@ -398,95 +328,107 @@ fn run_worker(
         .checked_sub(debounce)
         .unwrap_or_else(std::time::Instant::now);
 
-    // Last content (to avoid duplicates)
-    let mut last_content: Option<String> = None;
+    // Last emitted payload's dedup hash, tracked per selection so that e.g.
+    // re-highlighting the same text into PRIMARY doesn't suppress a genuine
+    // new copy into CLIPBOARD, and vice versa. Hashing lets this stay cheap
+    // even for multi-megabyte images.
+    let mut last_hash: HashMap<ClipboardSelection, String> = HashMap::new();
 
     info!("Clipboard worker started");
 
-    // Simple loop: wait for pulse, debounce, read, filter, and send events
+    // Simple loop: wait for pulse(s), debounce, read, filter, and send events
     loop {
         // Wait for the next pulse; exit if the channel is closed
-        if rx.recv().is_err() {
-            break; // Channel closed (stop)
-        }
+        let first = match rx.recv() {
+            Ok(selection) => selection,
+            Err(_) => break, // Channel closed (stop)
+        };
+
+        let mut pending: Vec<ClipboardSelection> = vec![first];
 
         // Debounce: if the time since the last processing is less than debounce, sleep to merge events
         let since = last_emit.elapsed();
         if since < debounce {
             std::thread::sleep(debounce - since);
-
-            // Merge redundant pulses (non-blocking attempt to clear the queue)
-            while rx.try_recv().is_ok() {}
         }
 
-        let start_time = std::time::Instant::now();
-
-        // Retry reading
-        let content_opt = read_clipboard_with_retry(
-            &mut clipboard,
-            config.retry_max,
-            config.retry_initial_delay_ms,
-        );
+        // Merge redundant/simultaneous pulses (non-blocking drain), keeping
+        // track of every distinct selection that changed so none get dropped.
+        while let Ok(selection) = rx.try_recv() {
+            if !pending.contains(&selection) {
+                pending.push(selection);
+            }
+        }
 
         last_emit = std::time::Instant::now();
 
-        let mut current_content = match content_opt {
-            Some(s) => s,
-            None => {
-                debug!("Worker: clipboard read failed after retries, skip");
-                continue;
-            }
-        };
+        for selection in pending {
+            let start_time = std::time::Instant::now();
+
+            let payload = match read_clipboard_payload(
+                &mut clipboard,
+                &config,
+                selection,
+                config.retry_max,
+                config.retry_initial_delay_ms,
+            ) {
+                Some(p) => p,
+                None => {
+                    debug!("Worker: clipboard read failed after retries for {:?}, skip", selection);
+                    continue;
+                }
+            };
 
-        // filter
-        if current_content.len() < config.min_content_length {
-            debug!("Worker: content too short, ignored: {} chars", current_content.len());
-            continue;
-        }
-        if current_content.len() > config.max_content_length {
-            debug!("Worker: content too long, ignored: {} chars", current_content.len());
-            continue;
-        }
-        let trimmed = current_content.trim();
-        if trimmed.is_empty() {
-            debug!("Worker: empty/whitespace content, ignored");
-            continue;
-        }
+            // filter (text-oriented limits only apply to text payloads)
+            if let ClipboardPayload::Text(ref text) = payload {
+                if text.len() < config.min_content_length {
+                    debug!("Worker: content too short, ignored: {} chars", text.len());
+                    continue;
+                }
+                if text.len() > config.max_content_length {
+                    debug!("Worker: content too long, ignored: {} chars", text.len());
+                    continue;
+                }
+                let trimmed = text.trim();
+                if trimmed.is_empty() {
+                    debug!("Worker: empty/whitespace content, ignored");
+                    continue;
+                }
+                if config.ignore_short_content && trimmed.len() <= 1 {
+                    debug!("Worker: very short content, ignored: '{}'", trimmed);
+                    continue;
+                }
+            }
 
-        // ignore duplicates
-        if config.ignore_duplicates {
-            if let Some(ref last) = last_content {
-                if last == &current_content {
-                    debug!("Worker: duplicate content, ignored");
+            let hash = payload_dedup_hash(&payload);
+            if config.ignore_duplicates {
+                if last_hash.get(&selection).map(String::as_str) == Some(hash.as_str()) {
+                    debug!("Worker: duplicate content for {:?}, ignored", selection);
                     continue;
                 }
             }
-        }
-        if config.ignore_short_content && trimmed.len() <= 1 {
-            debug!("Worker: very short content, ignored: '{}'", trimmed);
-            continue;
-        }
 
-        // event creation and sending
-        let event = content_detector.create_event(current_content.clone(), None);
-        last_content = Some(std::mem::take(&mut current_content));
+            // event creation and sending
+            let event = content_detector.create_event_for_selection(payload, None, selection);
+            last_hash.insert(selection, hash);
 
-        info!(
-            "Clipboard change detected (worker): {} characters, type: {:?}",
-            event.content_length, event.content_type
-        );
+            info!(
+                "Clipboard change detected (worker): {:?}, {} bytes, type: {:?}",
+                selection, event.content_length, event.content_type
+            );
 
-        let detection_ms = start_time.elapsed().as_millis() as u64;
-        let change = ClipboardChange {
-            event,
-            is_duplicate: false,
-            source_detection_time_ms: detection_ms,
-        };
+            let detection_ms = start_time.elapsed().as_millis() as u64;
+            let change = ClipboardChange {
+                event,
+                is_duplicate: false,
+                source_detection_time_ms: detection_ms,
+            };
 
-        if let Err(e) = event_sender.send(change) {
-            warn!("Worker: failed to send clipboard event: {}", e);
-        } else {
-            debug!("Worker: event sent ({}ms)", detection_ms);
+            if let Err(e) = event_sender.send(change) {
+                warn!("Worker: failed to send clipboard event: {}", e);
+            } else {
+                debug!("Worker: event sent ({}ms)", detection_ms);
+            }
         }
     }
 
@@ -494,24 +436,52 @@ fn run_worker(
     Ok(())
 }
 
-/// read clipboard with retries and exponential backoff
-fn read_clipboard_with_retry(
+/// Hash used purely for in-worker dedup (separate from `ClipboardEvent`'s
+/// own content_hash, though derived the same way) so we can compare before
+/// paying the cost of building a full event.
+fn payload_dedup_hash(payload: &ClipboardPayload) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    match payload {
+        ClipboardPayload::Text(s) => s.hash(&mut hasher),
+        ClipboardPayload::Image { png_bytes, .. } => png_bytes.hash(&mut hasher),
+        ClipboardPayload::Files(paths) => paths.hash(&mut hasher),
+    }
+    format!("{:x}", hasher.finish())
+}
+
+/// Read whatever `selection` currently holds, with retries and exponential
+/// backoff. Always tries text first (the common case); if there's no text
+/// and the caller opted into `AcceptedFormat::Image`, falls back to reading
+/// image data and encoding it to PNG for a stable representation. Image
+/// capture only applies to the regular `Clipboard` selection - `PRIMARY`/
+/// `SECONDARY` are highlight-to-copy text selections and don't carry images.
+fn read_clipboard_payload(
     clipboard: &mut Clipboard,
+    config: &MonitorConfig,
+    selection: ClipboardSelection,
     retry_max: u32,
     initial_delay_ms: u64,
-) -> Option<String> {
+) -> Option<ClipboardPayload> {
     let mut delay = std::time::Duration::from_millis(initial_delay_ms.max(1));
     let max_delay = std::time::Duration::from_millis(200);
+    let want_images = selection == ClipboardSelection::Clipboard
+        && config.accepted_formats.contains(&AcceptedFormat::Image);
 
     for attempt in 0..retry_max {
-        match clipboard.get_text() {
-            Ok(s) => return Some(s),
+        match read_text_for_selection(clipboard, selection) {
+            Ok(s) => return Some(ClipboardPayload::Text(s)),
             Err(arboard::Error::ContentNotAvailable) => {
-                // content not available (clipboard empty or non-text)
+                if want_images {
+                    if let Some(payload) = try_read_image(clipboard) {
+                        return Some(payload);
+                    }
+                }
                 debug!("Clipboard read: ContentNotAvailable (attempt {}/{})", attempt + 1, retry_max);
             }
             Err(e) => {
-                // other errors (e.g., clipboard locked)
                 debug!("Clipboard read error (attempt {}/{}): {}", attempt + 1, retry_max, e);
             }
         }
@@ -523,6 +493,89 @@ fn read_clipboard_with_retry(
     None
 }
 
+/// Read text from the given selection. On Linux this uses arboard's X11
+/// selection extension to distinguish CLIPBOARD/PRIMARY/SECONDARY; other
+/// platforms only ever see `ClipboardSelection::Clipboard` (enforced by
+/// their backends), so they just use the regular clipboard API.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn read_text_for_selection(
+    clipboard: &mut Clipboard,
+    selection: ClipboardSelection,
+) -> Result<String, arboard::Error> {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+
+    let kind = match selection {
+        ClipboardSelection::Clipboard => LinuxClipboardKind::Clipboard,
+        ClipboardSelection::Primary => LinuxClipboardKind::Primary,
+        ClipboardSelection::Secondary => LinuxClipboardKind::Secondary,
+    };
+    clipboard.get().clipboard(kind).text()
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn read_text_for_selection(
+    clipboard: &mut Clipboard,
+    _selection: ClipboardSelection,
+) -> Result<String, arboard::Error> {
+    clipboard.get_text()
+}
+
+/// Write `content` into the given selection. Mirrors `read_text_for_selection`:
+/// on Linux this drives arboard's X11 selection extension so `copy_item_to_clipboard`
+/// can put text back into PRIMARY/SECONDARY instead of only CLIPBOARD; other
+/// platforms only have one clipboard, so `Primary`/`Secondary` fall back to it.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn write_text_for_selection(
+    clipboard: &mut Clipboard,
+    selection: ClipboardSelection,
+    content: &str,
+) -> Result<(), arboard::Error> {
+    use arboard::{LinuxClipboardKind, SetExtLinux};
+
+    let kind = match selection {
+        ClipboardSelection::Clipboard => LinuxClipboardKind::Clipboard,
+        ClipboardSelection::Primary => LinuxClipboardKind::Primary,
+        ClipboardSelection::Secondary => LinuxClipboardKind::Secondary,
+    };
+    clipboard.set().clipboard(kind).text(content)
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub(crate) fn write_text_for_selection(
+    clipboard: &mut Clipboard,
+    _selection: ClipboardSelection,
+    content: &str,
+) -> Result<(), arboard::Error> {
+    clipboard.set_text(content)
+}
+
+/// Read an image off the clipboard and encode it to PNG so the stored
+/// payload has one stable representation regardless of the native pixel
+/// format arboard handed us.
+fn try_read_image(clipboard: &mut Clipboard) -> Option<ClipboardPayload> {
+    let image_data = match clipboard.get_image() {
+        Ok(img) => img,
+        Err(e) => {
+            debug!("Clipboard image read failed: {}", e);
+            return None;
+        }
+    };
+
+    let width = image_data.width as u32;
+    let height = image_data.height as u32;
+
+    let buffer = image::RgbaImage::from_raw(width, height, image_data.bytes.into_owned())?;
+    let mut png_bytes = Vec::new();
+    if let Err(e) = image::DynamicImage::ImageRgba8(buffer)
+        .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+    {
+        warn!("Failed to encode clipboard image to PNG: {}", e);
+        return None;
+    }
+
+    Some(ClipboardPayload::Image { width, height, png_bytes })
+}
+
 // ===== 測試 =====
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,244 @@
+// src-tauri/src/clipboard/sync.rs
+//! Network clipboard sync: periodically pushes local clips to a configured
+//! peer/relay and pulls back whatever it collected from other devices, so a
+//! URL copied on one machine shows up in the history popup on another.
+//!
+//! This module only knows about `SyncedItem`, a flat, serializable stand-in
+//! for `lib::ClipboardItem` - it doesn't depend on the crate root, the same
+//! way `history::ClipboardHistoryStore` doesn't know about the popup. The
+//! caller (`lib.rs`) supplies a `local_source` (what to push) and
+//! `remote_sink` (where pulled items land) when calling `start_msg_sync`, so
+//! merging into `CLIPBOARD_HISTORY` and respecting `MAX_HISTORY_SIZE` stays
+//! lib.rs's job. Remote items only ever flow through `remote_sink` - they
+//! never touch the AI popup, which only fires off the local monitor's own
+//! `ClipboardChange` stream.
+
+use log::{info, warn};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Content types that are never pushed to the relay, regardless of sync
+/// configuration - e.g. a `Financial` clip can carry an amount or account
+/// number that shouldn't leave the machine just because the rest of history
+/// is being synced.
+const NEVER_SYNCED_TYPES: &[&str] = &["Financial"];
+
+/// One clip as sent to / received from the sync relay. Field set mirrors
+/// `lib::ClipboardItem`, plus `origin_device` so a puller can tell a remote
+/// item apart from its own content echoed back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedItem {
+    pub id: String,
+    pub content: String,
+    pub content_type: String,
+    pub timestamp: String,
+    pub content_length: usize,
+    pub content_preview: String,
+    pub selection: String,
+    pub origin_device: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    pub endpoint: String,
+    pub secret: String,
+    pub interval: Duration,
+}
+
+impl SyncConfig {
+    pub fn new(endpoint: String, secret: String) -> Self {
+        Self {
+            endpoint,
+            secret,
+            interval: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub running: bool,
+    pub device_id: String,
+    pub endpoint: String,
+    pub pushed_count: u64,
+    pub pulled_count: u64,
+    pub last_error: Option<String>,
+}
+
+/// Supplies the items worth offering to the relay on this tick (already
+/// filtered to "not yet pushed" is the caller's choice - `SyncState` also
+/// tracks `pushed_ids` itself so the same clip is never pushed twice even if
+/// `local_source` returns it again).
+pub type LocalSource = Box<dyn Fn() -> Vec<SyncedItem> + Send + Sync>;
+/// Receives whatever `pull` got back from the relay that didn't come from
+/// this device and hasn't been seen before.
+pub type RemoteSink = Box<dyn Fn(Vec<SyncedItem>) + Send + Sync>;
+
+pub struct SyncState {
+    config: SyncConfig,
+    device_id: String,
+    running: std::sync::atomic::AtomicBool,
+    pushed_ids: Mutex<HashSet<String>>,
+    pushed_count: std::sync::atomic::AtomicU64,
+    pulled_count: std::sync::atomic::AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl SyncState {
+    pub fn status(&self) -> SyncStatus {
+        use std::sync::atomic::Ordering;
+        SyncStatus {
+            running: self.running.load(Ordering::Relaxed),
+            device_id: self.device_id.clone(),
+            endpoint: self.config.endpoint.clone(),
+            pushed_count: self.pushed_count.load(Ordering::Relaxed),
+            pulled_count: self.pulled_count.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().unwrap().clone(),
+        }
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn record_error(&self, message: String) {
+        warn!("Clipboard sync error: {}", message);
+        *self.last_error.lock().unwrap() = Some(message);
+    }
+}
+
+/// Spawns the background sync loop and returns the shared state a caller
+/// can poll (`status`) or halt (`stop`) without tearing down the task
+/// itself - the loop checks `running` every tick and exits once it's false.
+pub fn start_msg_sync(
+    config: SyncConfig,
+    device_id: String,
+    local_source: LocalSource,
+    remote_sink: RemoteSink,
+) -> Arc<SyncState> {
+    let state = Arc::new(SyncState {
+        config: config.clone(),
+        device_id: device_id.clone(),
+        running: std::sync::atomic::AtomicBool::new(true),
+        pushed_ids: Mutex::new(HashSet::new()),
+        pushed_count: std::sync::atomic::AtomicU64::new(0),
+        pulled_count: std::sync::atomic::AtomicU64::new(0),
+        last_error: Mutex::new(None),
+    });
+
+    let task_state = state.clone();
+    tokio::spawn(async move {
+        info!("Clipboard sync started (device {}, endpoint {})", device_id, config.endpoint);
+        let client = Client::new();
+        let mut ticker = tokio::time::interval(config.interval);
+
+        loop {
+            ticker.tick().await;
+            if !task_state.running.load(std::sync::atomic::Ordering::Relaxed) {
+                break;
+            }
+
+            push_new_items(&client, &config, &device_id, &local_source, &task_state).await;
+            pull_remote_items(&client, &config, &device_id, &remote_sink, &task_state).await;
+        }
+
+        info!("Clipboard sync stopped (device {})", device_id);
+    });
+
+    state
+}
+
+async fn push_new_items(
+    client: &Client,
+    config: &SyncConfig,
+    device_id: &str,
+    local_source: &LocalSource,
+    state: &Arc<SyncState>,
+) {
+    let candidates = local_source();
+    let to_push: Vec<SyncedItem> = {
+        let mut pushed_ids = state.pushed_ids.lock().unwrap();
+        candidates
+            .into_iter()
+            .filter(|item| !NEVER_SYNCED_TYPES.contains(&item.content_type.as_str()))
+            .filter(|item| pushed_ids.insert(item.id.clone()))
+            .collect()
+    };
+
+    if to_push.is_empty() {
+        return;
+    }
+    let pushed = to_push.len() as u64;
+
+    let body = serde_json::json!({
+        "device_id": device_id,
+        "items": to_push,
+    });
+
+    let result = client
+        .post(format!("{}/push", config.endpoint))
+        .header("X-ClipMind-Secret", &config.secret)
+        .json(&body)
+        .send()
+        .await;
+
+    match result {
+        Ok(resp) if resp.status().is_success() => {
+            state.pushed_count.fetch_add(pushed, std::sync::atomic::Ordering::Relaxed);
+        }
+        Ok(resp) => state.record_error(format!("Push rejected by relay: {}", resp.status())),
+        Err(e) => state.record_error(format!("Push failed: {}", e)),
+    }
+}
+
+async fn pull_remote_items(
+    client: &Client,
+    config: &SyncConfig,
+    device_id: &str,
+    remote_sink: &RemoteSink,
+    state: &Arc<SyncState>,
+) {
+    let result = client
+        .get(format!("{}/pull", config.endpoint))
+        .header("X-ClipMind-Secret", &config.secret)
+        .query(&[("exclude_device", device_id)])
+        .send()
+        .await;
+
+    let response = match result {
+        Ok(resp) if resp.status().is_success() => resp,
+        Ok(resp) => {
+            state.record_error(format!("Pull rejected by relay: {}", resp.status()));
+            return;
+        }
+        Err(e) => {
+            state.record_error(format!("Pull failed: {}", e));
+            return;
+        }
+    };
+
+    let items: Vec<SyncedItem> = match response.json().await {
+        Ok(items) => items,
+        Err(e) => {
+            state.record_error(format!("Pull response parse failed: {}", e));
+            return;
+        }
+    };
+
+    // Never let a device merge its own pushes back in, even if the relay
+    // didn't honor `exclude_device`.
+    let remote_only: Vec<SyncedItem> = items
+        .into_iter()
+        .filter(|item| item.origin_device.as_deref() != Some(device_id))
+        .collect();
+
+    if remote_only.is_empty() {
+        return;
+    }
+
+    state.pulled_count.fetch_add(remote_only.len() as u64, std::sync::atomic::Ordering::Relaxed);
+    remote_sink(remote_only);
+}
@@ -11,6 +11,7 @@ pub enum BasicContentType {
     DateTime,
     Code,
     Address,
+    Image,
     PlainText,
 }
 
@@ -28,10 +29,84 @@ pub enum ContentType {
     Unknown,
 }
 
+/// Which X11/Wayland selection a clip came from. Windows and macOS only
+/// have one clipboard, so they always report `Clipboard`; on Linux,
+/// `Primary` (set by highlighting text) and `Secondary` (rarely used, but
+/// part of the ICCCM) are distinct selections with their own owners and
+/// their own dedup state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClipboardSelection {
+    Clipboard,
+    Primary,
+    Secondary,
+}
+
+impl Default for ClipboardSelection {
+    fn default() -> Self {
+        ClipboardSelection::Clipboard
+    }
+}
+
+/// What was actually sitting on the clipboard. `Text` is still the common
+/// case and is what most of the pipeline (detector, rule engine, AI engine)
+/// understands today; `Image`/`Files` let the monitor and history carry
+/// non-text clips without forcing every consumer to handle them yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClipboardPayload {
+    Text(String),
+    Image {
+        width: u32,
+        height: u32,
+        png_bytes: Vec<u8>,
+    },
+    Files(Vec<std::path::PathBuf>),
+}
+
+impl ClipboardPayload {
+    /// Bytes used both for hashing and for deciding whether a payload is
+    /// "the same" as a previous one, independent of its variant.
+    fn dedup_bytes(&self) -> Vec<u8> {
+        match self {
+            ClipboardPayload::Text(s) => s.as_bytes().to_vec(),
+            ClipboardPayload::Image { png_bytes, .. } => png_bytes.clone(),
+            ClipboardPayload::Files(paths) => paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("\n")
+                .into_bytes(),
+        }
+    }
+
+    /// A short, human/debug-friendly preview used wherever the pipeline
+    /// still expects a plain string (e.g. `content` below, prompts).
+    pub fn text_preview(&self) -> String {
+        match self {
+            ClipboardPayload::Text(s) => s.clone(),
+            ClipboardPayload::Image { width, height, png_bytes } => {
+                format!("[image {}x{}, {} bytes]", width, height, png_bytes.len())
+            }
+            ClipboardPayload::Files(paths) => paths
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardEvent {
+    /// Text representation of the payload; for `Image`/`Files` this is a
+    /// preview string rather than the literal clipboard bytes, kept so the
+    /// existing text-oriented pipeline (detector, rule/AI engines) still has
+    /// something to work with until they're taught about `payload` directly.
     pub content: String,
+    pub payload: ClipboardPayload,
     pub content_type: BasicContentType,
+    /// Which selection (CLIPBOARD/PRIMARY/SECONDARY) this came from. Always
+    /// `Clipboard` on platforms with only one clipboard.
+    pub selection: ClipboardSelection,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub source_app: Option<String>,
     pub content_hash: String,
@@ -39,12 +114,17 @@ pub struct ClipboardEvent {
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RuleAnalysis {
     pub confidence: f32,
     pub metadata: HashMap<String, String>,
     pub suggested_actions: Vec<ActionSuggestion>,
     pub needs_ai_analysis: bool,
+    /// Content with detected PII/secrets masked out; `None` if nothing was
+    /// flagged, in which case the original content is safe to use as-is.
+    pub redacted_content: Option<String>,
+    /// 0.0 (nothing sensitive found) to 1.0 (several categories found).
+    pub sensitivity: f32,
 }
 
 
@@ -98,6 +178,37 @@ pub struct UserContext {
     pub recent_actions: Vec<String>,
     pub time_of_day: String,
     pub app_context: Option<String>,
+    /// Set when `ContentDetector::detect_ranked`'s top two scores were close
+    /// enough that the runner-up type is worth the AI engine knowing about
+    /// (e.g. "Financial (0.61)" alongside a primary type of `Url`).
+    pub secondary_candidate: Option<String>,
+}
+
+/// One task in a `process_ai_pipeline` chain (e.g. `task_type: "summarize"`
+/// then `task_type: "translate"`, so a foreign-language article can be
+/// condensed and translated in a single call).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStep {
+    pub task_type: String,
+    pub parameters: Option<HashMap<String, String>>,
+}
+
+/// Result of running a `process_ai_pipeline` chain: the final step's output
+/// plus every intermediate output, in step order, so callers can surface
+/// "summarized to: ..." alongside the final translation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineResult {
+    pub final_output: String,
+    pub step_outputs: Vec<String>,
+}
+
+/// Result of `ContentAnalyzer::ask_history`/`AiEngine::answer_with_sources`:
+/// the model's answer plus which history entries (`content_hash`s) it
+/// actually cited, so the UI can link back to the source clips.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagAnswer {
+    pub answer: String,
+    pub cited_entry_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,22 +258,70 @@ pub enum ClipboardError {
 
 impl ClipboardEvent {
     pub fn new(content: String, content_type: BasicContentType, source_app: Option<String>) -> Self {
+        Self::new_for_selection(content, content_type, source_app, ClipboardSelection::default())
+    }
+
+    pub fn new_for_selection(
+        content: String,
+        content_type: BasicContentType,
+        source_app: Option<String>,
+        selection: ClipboardSelection,
+    ) -> Self {
+        let content_length = content.len();
+        let payload = ClipboardPayload::Text(content.clone());
         Self {
-            content: content.clone(),
+            content_hash: Self::calculate_hash(&payload),
+            content,
+            payload,
             content_type,
+            selection,
             timestamp: chrono::Utc::now(),
             source_app,
-            content_hash: Self::calculate_hash(&content),
-            content_length: content.len(),
+            content_length,
         }
     }
-    
-    fn calculate_hash(content: &str) -> String {
+
+    pub fn from_payload(
+        payload: ClipboardPayload,
+        content_type: BasicContentType,
+        source_app: Option<String>,
+    ) -> Self {
+        Self::from_payload_for_selection(payload, content_type, source_app, ClipboardSelection::default())
+    }
+
+    pub fn from_payload_for_selection(
+        payload: ClipboardPayload,
+        content_type: BasicContentType,
+        source_app: Option<String>,
+        selection: ClipboardSelection,
+    ) -> Self {
+        let content = payload.text_preview();
+        let content_length = match &payload {
+            ClipboardPayload::Text(s) => s.len(),
+            ClipboardPayload::Image { png_bytes, .. } => png_bytes.len(),
+            ClipboardPayload::Files(_) => content.len(),
+        };
+        Self {
+            content_hash: Self::calculate_hash(&payload),
+            content,
+            payload,
+            content_type,
+            selection,
+            timestamp: chrono::Utc::now(),
+            source_app,
+            content_length,
+        }
+    }
+
+    /// Dedup/identity hash for a payload. Hashing the raw bytes (rather
+    /// than comparing strings) means a multi-megabyte image is compared in
+    /// one pass instead of on every subsequent string equality check.
+    fn calculate_hash(payload: &ClipboardPayload) -> String {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
-        
+
         let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
+        payload.dedup_bytes().hash(&mut hasher);
         format!("{:x}", hasher.finish())
     }
 }
@@ -0,0 +1,102 @@
+// src-tauri/src/history/embedder.rs
+//! Sentence embeddings for semantic clipboard recall.
+//!
+//! Kept behind a trait so the actual model is pluggable: `HashEmbedder`
+//! below is a small, dependency-free local embedding (feature hashing)
+//! that works offline out of the box; a remote embeddings endpoint (e.g.
+//! an OpenAI-compatible `/v1/embeddings` call) can implement the same
+//! trait without touching `ClipboardHistoryStore`.
+
+/// Produces a fixed-size, L2-normalized embedding for a piece of text.
+/// Implementations MUST return a normalized vector (unit length) so
+/// callers can compute cosine similarity as a plain dot product.
+pub trait Embedder: Send + Sync {
+    fn dimensions(&self) -> usize;
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+/// Deterministic local embedding via feature hashing: every whitespace
+/// token is hashed into one of `dimensions` buckets and accumulated, then
+/// the resulting vector is L2-normalized. It's not a real semantic model,
+/// but it's stable, offline, and dependency-free, making it a reasonable
+/// default until a local model or remote endpoint is wired in.
+pub struct HashEmbedder {
+    dimensions: usize,
+}
+
+impl HashEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self { dimensions }
+    }
+}
+
+impl Default for HashEmbedder {
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+impl Embedder for HashEmbedder {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0f32; self.dimensions];
+
+        for token in text.split_whitespace() {
+            let bucket = (hash_token(token) as usize) % self.dimensions;
+            vector[bucket] += 1.0;
+        }
+
+        normalize(&mut vector);
+        vector
+    }
+}
+
+fn hash_token(token: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    token.to_lowercase().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// L2-normalize `vector` in place; leaves an all-zero vector untouched
+/// (e.g. empty input) rather than dividing by zero.
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        for x in vector.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Cosine similarity between two already-normalized vectors is just their
+/// dot product.
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_is_normalized() {
+        let embedder = HashEmbedder::new(32);
+        let vector = embedder.embed("hello world this is a test");
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5 || norm == 0.0);
+    }
+
+    #[test]
+    fn test_identical_text_has_similarity_one() {
+        let embedder = HashEmbedder::new(32);
+        let a = embedder.embed("copy this address to maps");
+        let b = embedder.embed("copy this address to maps");
+        assert!((dot(&a, &b) - 1.0).abs() < 1e-5);
+    }
+}
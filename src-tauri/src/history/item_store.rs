@@ -0,0 +1,438 @@
+// src-tauri/src/history/item_store.rs
+//! Persistent backing for the frontend-facing clipboard history list
+//! (`get_clipboard_history`/`search_clipboard_history`/...), replacing the
+//! old `Mutex<VecDeque<ClipboardItem>>` that was capped at 100 rows and
+//! lost on every restart.
+//!
+//! Modeled on `ClipboardHistoryStore`'s SQLite-backed design, but keyed by
+//! the frontend's `id` (a UUID per capture) rather than a content hash,
+//! since callers (`copy_image_to_clipboard`, clipboard sync) already look
+//! items up by id. Insertion order is tracked with SQLite's implicit
+//! `rowid` instead of a timestamp column, so `list`/`search` can order
+//! "most recently copied first" with a plain `ORDER BY rowid DESC`.
+
+use crate::clipboard::types::ClipboardError;
+use crate::ClipboardItem;
+use log::warn;
+use rusqlite::{params, Connection, Row};
+use std::sync::Mutex;
+
+/// How long a `Financial`/`Phone` item (card numbers, phone numbers - the
+/// content types most likely to carry something sensitive) is kept before
+/// `prune_expired` deletes it, regardless of the overall `max_entries` cap.
+/// Short enough that a finance/contact clip doesn't linger in a persistent
+/// database indefinitely just because the user never re-copies over it.
+const SENSITIVE_TTL_SECS: i64 = 15 * 60;
+
+pub struct ClipboardItemStore {
+    conn: Mutex<Connection>,
+    max_entries: usize,
+}
+
+impl ClipboardItemStore {
+    pub fn open(db_path: &str, max_entries: usize) -> Result<Self, ClipboardError> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to open clipboard items db: {}", e)))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS clipboard_items (
+                id TEXT PRIMARY KEY,
+                content TEXT NOT NULL,
+                content_type TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                content_length INTEGER NOT NULL,
+                content_preview TEXT NOT NULL,
+                selection TEXT NOT NULL,
+                image_thumbnail TEXT,
+                image_full TEXT,
+                analysis TEXT,
+                created_at INTEGER NOT NULL,
+                expires_at INTEGER
+            )",
+            [],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to create clipboard_items table: {}", e)))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_entries,
+        })
+    }
+
+    /// Inserts a freshly-captured item (see `handle_clipboard_change_with_ai`).
+    /// Replaces any existing row with the same id, which also moves it to
+    /// the front of `rowid` order - the same effect `VecDeque::push_front`
+    /// had for a (rare) re-inserted id.
+    pub fn insert(&self, item: &ClipboardItem) -> Result<(), ClipboardError> {
+        let now = now_unix();
+        let expires_at = sensitive_ttl_expiry(&item.content_type, now);
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO clipboard_items
+                (id, content, content_type, timestamp, content_length, content_preview,
+                 selection, image_thumbnail, image_full, analysis, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, ?10, ?11)",
+            params![
+                item.id,
+                item.content,
+                item.content_type,
+                item.timestamp,
+                item.content_length as i64,
+                item.content_preview,
+                item.selection,
+                item.image_thumbnail,
+                item.image_full,
+                now,
+                expires_at,
+            ],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to insert clipboard item: {}", e)))?;
+
+        drop(conn);
+        self.prune_expired();
+        self.evict_if_needed()?;
+        Ok(())
+    }
+
+    /// Inserts `item` only if its id isn't already present, for clipboard
+    /// sync's pulled items (see `start_clipboard_sync`): re-pulling an item
+    /// the local store already has is a no-op, not a duplicate/refresh.
+    /// Returns whether it was actually inserted.
+    pub fn insert_if_absent(&self, item: &ClipboardItem) -> bool {
+        let now = now_unix();
+        let expires_at = sensitive_ttl_expiry(&item.content_type, now);
+
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO clipboard_items
+                    (id, content, content_type, timestamp, content_length, content_preview,
+                     selection, image_thumbnail, image_full, analysis, created_at, expires_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, ?10, ?11)",
+                params![
+                    item.id,
+                    item.content,
+                    item.content_type,
+                    item.timestamp,
+                    item.content_length as i64,
+                    item.content_preview,
+                    item.selection,
+                    item.image_thumbnail,
+                    item.image_full,
+                    now,
+                    expires_at,
+                ],
+            )
+            .map(|changed| changed > 0)
+            .unwrap_or(false);
+
+        drop(conn);
+        if inserted {
+            self.prune_expired();
+            let _ = self.evict_if_needed();
+        }
+        inserted
+    }
+
+    /// Every stored item, most recently copied first, up to `limit`.
+    pub fn list(&self, limit: usize) -> Vec<ClipboardItem> {
+        self.query(
+            "SELECT id, content, content_type, timestamp, content_length, content_preview,
+                    selection, image_thumbnail, image_full
+             FROM clipboard_items ORDER BY rowid DESC LIMIT ?1",
+            params![limit as i64],
+        )
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<ClipboardItem> {
+        self.query(
+            "SELECT id, content, content_type, timestamp, content_length, content_preview,
+                    selection, image_thumbnail, image_full
+             FROM clipboard_items WHERE id = ?1",
+            params![id],
+        )
+        .into_iter()
+        .next()
+    }
+
+    /// Substring content match (case-insensitive, via SQLite's default
+    /// `LIKE` collation) and/or `content_type` filter, most recently copied
+    /// first. Either filter may be omitted to match everything. The clause
+    /// list (and its bound params) is built up dynamically since either
+    /// filter can be absent, rather than keeping two/three near-duplicate
+    /// hand-written queries around.
+    pub fn search(&self, query: Option<&str>, content_type: Option<&str>, limit: usize) -> Vec<ClipboardItem> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut sql = String::from(
+            "SELECT id, content, content_type, timestamp, content_length, content_preview,
+                    selection, image_thumbnail, image_full
+             FROM clipboard_items WHERE 1 = 1",
+        );
+        let mut bind: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(q) = query {
+            sql.push_str(" AND content LIKE ?");
+            bind.push(Box::new(format!("%{}%", q)));
+        }
+        if let Some(ct) = content_type {
+            sql.push_str(" AND content_type = ?");
+            bind.push(Box::new(ct.to_string()));
+        }
+        sql.push_str(" ORDER BY rowid DESC LIMIT ?");
+        bind.push(Box::new(limit as i64));
+
+        let mut stmt = match conn.prepare(&sql) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                warn!("search: failed to prepare query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map(rusqlite::params_from_iter(bind), Self::row_to_item);
+
+        match rows {
+            Ok(rows) => rows.flatten().collect(),
+            Err(e) => {
+                warn!("search: failed to run query: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn clear(&self) -> Result<(), ClipboardError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM clipboard_items", [])
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to clear clipboard items: {}", e)))?;
+        Ok(())
+    }
+
+    /// Caches a computed `CompleteAnalysis` (serialized as JSON by the
+    /// caller) against an item, so a later `get_ai_suggestions` call for
+    /// the same item can skip reanalyzing it. See `cached_analysis`.
+    pub fn record_analysis(&self, id: &str, analysis_json: &str) -> Result<(), ClipboardError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE clipboard_items SET analysis = ?1 WHERE id = ?2",
+            params![analysis_json, id],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to record analysis: {}", e)))?;
+        Ok(())
+    }
+
+    pub fn cached_analysis(&self, id: &str) -> Option<String> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT analysis FROM clipboard_items WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .ok()
+        .flatten()
+    }
+
+    fn query(&self, sql: &str, query_params: impl rusqlite::Params) -> Vec<ClipboardItem> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = match conn.prepare(sql) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                warn!("clipboard item query failed to prepare: {}", e);
+                return Vec::new();
+            }
+        };
+
+        match stmt.query_map(query_params, Self::row_to_item) {
+            Ok(rows) => rows.flatten().collect(),
+            Err(e) => {
+                warn!("clipboard item query failed to run: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn row_to_item(row: &Row) -> rusqlite::Result<ClipboardItem> {
+        Ok(ClipboardItem {
+            id: row.get(0)?,
+            content: row.get(1)?,
+            content_type: row.get(2)?,
+            timestamp: row.get(3)?,
+            content_length: row.get::<_, i64>(4)? as usize,
+            content_preview: row.get(5)?,
+            selection: row.get(6)?,
+            image_thumbnail: row.get(7)?,
+            image_full: row.get(8)?,
+        })
+    }
+
+    /// Deletes rows past their `expires_at` (see `SENSITIVE_TTL_SECS`).
+    /// Best-effort: failures are logged rather than propagated since this
+    /// runs as a side effect of every insert, not a user-facing operation.
+    fn prune_expired(&self) {
+        let now = now_unix();
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "DELETE FROM clipboard_items WHERE expires_at IS NOT NULL AND expires_at < ?1",
+            params![now],
+        ) {
+            warn!("Failed to prune expired clipboard items: {}", e);
+        }
+    }
+
+    /// Evicts the oldest rows (by `rowid`) once the table grows past
+    /// `max_entries`, the count-based half of the "age/count" pruning the
+    /// TTL above handles by age.
+    fn evict_if_needed(&self) -> Result<(), ClipboardError> {
+        let conn = self.conn.lock().unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM clipboard_items", [], |row| row.get(0))
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to count clipboard items: {}", e)))?;
+
+        let excess = count - self.max_entries as i64;
+        if excess <= 0 {
+            return Ok(());
+        }
+
+        conn.execute(
+            "DELETE FROM clipboard_items WHERE id IN (
+                SELECT id FROM clipboard_items ORDER BY rowid ASC LIMIT ?1
+            )",
+            params![excess],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to evict clipboard items: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// `Some(expiry unix timestamp)` for content types that shouldn't linger in
+/// a persistent database, `None` for everything else (pruned only by the
+/// count-based `max_entries` cap).
+fn sensitive_ttl_expiry(content_type: &str, now: i64) -> Option<i64> {
+    match content_type {
+        "Financial" | "Phone" => Some(now + SENSITIVE_TTL_SECS),
+        _ => None,
+    }
+}
+
+fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(max_entries: usize) -> ClipboardItemStore {
+        let path = format!(
+            "{}/clipmind_items_test_{}.db",
+            std::env::temp_dir().display(),
+            uuid::Uuid::new_v4()
+        );
+        ClipboardItemStore::open(&path, max_entries).unwrap()
+    }
+
+    fn test_item(id: &str, content: &str, content_type: &str) -> ClipboardItem {
+        ClipboardItem {
+            id: id.to_string(),
+            content: content.to_string(),
+            content_type: content_type.to_string(),
+            timestamp: "2026-01-01 00:00:00".to_string(),
+            content_length: content.len(),
+            content_preview: content.to_string(),
+            selection: "Clipboard".to_string(),
+            image_thumbnail: None,
+            image_full: None,
+        }
+    }
+
+    #[test]
+    fn test_insert_and_list_newest_first() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "first", "PlainText")).unwrap();
+        store.insert(&test_item("b", "second", "PlainText")).unwrap();
+
+        let items = store.list(10);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "b");
+        assert_eq!(items[1].id, "a");
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "hello", "PlainText")).unwrap();
+        assert_eq!(store.find_by_id("a").unwrap().content, "hello");
+        assert!(store.find_by_id("missing").is_none());
+    }
+
+    #[test]
+    fn test_search_by_substring_and_content_type() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "https://example.com", "Url")).unwrap();
+        store.insert(&test_item("b", "just some text", "PlainText")).unwrap();
+
+        let url_results = store.search(None, Some("Url"), 10);
+        assert_eq!(url_results.len(), 1);
+        assert_eq!(url_results[0].id, "a");
+
+        let text_match = store.search(Some("example"), None, 10);
+        assert_eq!(text_match.len(), 1);
+        assert_eq!(text_match[0].id, "a");
+    }
+
+    #[test]
+    fn test_evict_caps_table_size() {
+        let store = temp_store(2);
+        for i in 0..5 {
+            store.insert(&test_item(&i.to_string(), "content", "PlainText")).unwrap();
+        }
+        assert_eq!(store.list(10).len(), 2);
+    }
+
+    #[test]
+    fn test_clear_removes_everything() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "hello", "PlainText")).unwrap();
+        store.clear().unwrap();
+        assert!(store.list(10).is_empty());
+    }
+
+    #[test]
+    fn test_sensitive_items_get_a_ttl() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "4111 1111 1111 1111", "Financial")).unwrap();
+        store.insert(&test_item("b", "just text", "PlainText")).unwrap();
+
+        let conn = store.conn.lock().unwrap();
+        let financial_expiry: Option<i64> = conn
+            .query_row("SELECT expires_at FROM clipboard_items WHERE id = 'a'", [], |row| row.get(0))
+            .unwrap();
+        let plain_expiry: Option<i64> = conn
+            .query_row("SELECT expires_at FROM clipboard_items WHERE id = 'b'", [], |row| row.get(0))
+            .unwrap();
+
+        assert!(financial_expiry.is_some());
+        assert!(plain_expiry.is_none());
+    }
+
+    #[test]
+    fn test_record_and_read_cached_analysis() {
+        let store = temp_store(10);
+        store.insert(&test_item("a", "hello", "PlainText")).unwrap();
+        assert!(store.cached_analysis("a").is_none());
+
+        store.record_analysis("a", "{\"cached\":true}").unwrap();
+        assert_eq!(store.cached_analysis("a").unwrap(), "{\"cached\":true}");
+    }
+
+    #[test]
+    fn test_insert_if_absent_skips_existing_id() {
+        let store = temp_store(10);
+        assert!(store.insert_if_absent(&test_item("a", "hello", "PlainText")));
+        assert!(!store.insert_if_absent(&test_item("a", "hello again", "PlainText")));
+        assert_eq!(store.list(10).len(), 1);
+    }
+}
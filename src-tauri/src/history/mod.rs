@@ -0,0 +1,8 @@
+// src-tauri/src/history/mod.rs
+pub mod embedder;
+pub mod item_store;
+pub mod store;
+
+pub use embedder::{Embedder, HashEmbedder};
+pub use item_store::ClipboardItemStore;
+pub use store::{ClipboardHistoryStore, HistoryMatch};
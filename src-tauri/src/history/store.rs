@@ -0,0 +1,392 @@
+// src-tauri/src/history/store.rs
+//! Persistent, semantically-searchable clipboard history.
+//!
+//! Every recorded `ClipboardEvent` gets an embedding (see `embedder.rs`)
+//! stored alongside it in SQLite, so `find_similar` can answer "what did
+//! the user copy before that's related to this" without re-embedding the
+//! whole table on every query - scores are a single dot product against
+//! vectors that are already L2-normalized at insert time.
+
+use super::embedder::{dot, Embedder};
+use crate::clipboard::types::{BasicContentType, ClipboardError, ClipboardEvent};
+
+use log::{debug, warn};
+use rusqlite::{params, Connection};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+
+pub struct ClipboardHistoryStore {
+    conn: Mutex<Connection>,
+    embedder: Arc<dyn Embedder>,
+    max_entries: usize,
+}
+
+impl ClipboardHistoryStore {
+    pub fn open(
+        db_path: &str,
+        embedder: Arc<dyn Embedder>,
+        max_entries: usize,
+    ) -> Result<Self, ClipboardError> {
+        let conn = Connection::open(db_path)
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to open history db: {}", e)))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS clipboard_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content_hash TEXT NOT NULL UNIQUE,
+                content TEXT NOT NULL,
+                content_type TEXT NOT NULL,
+                source_app TEXT,
+                timestamp TEXT NOT NULL,
+                embedding TEXT NOT NULL,
+                last_accessed INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to create history table: {}", e)))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            embedder,
+            max_entries,
+        })
+    }
+
+    /// Record an event, deduping on `content_hash`. A re-copy of content
+    /// already in the store just refreshes its `last_accessed` stamp
+    /// (bumping it to the front of the LRU ordering) instead of inserting
+    /// a duplicate row.
+    pub fn record(&self, event: &ClipboardEvent) -> Result<(), ClipboardError> {
+        let embedding = self.embedder.embed(&event.content);
+        let embedding_json = serde_json::to_string(&embedding)
+            .map_err(|e| ClipboardError::ParsingError(format!("Failed to serialize embedding: {}", e)))?;
+        let now = now_unix();
+
+        let conn = self.conn.lock().unwrap();
+
+        let updated = conn
+            .execute(
+                "UPDATE clipboard_history SET last_accessed = ?1 WHERE content_hash = ?2",
+                params![now, event.content_hash],
+            )
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to touch history row: {}", e)))?;
+
+        if updated == 0 {
+            conn.execute(
+                "INSERT INTO clipboard_history
+                    (content_hash, content, content_type, source_app, timestamp, embedding, last_accessed)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    event.content_hash,
+                    event.content,
+                    format!("{:?}", event.content_type),
+                    event.source_app,
+                    event.timestamp.to_rfc3339(),
+                    embedding_json,
+                    now,
+                ],
+            )
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to insert history row: {}", e)))?;
+        }
+
+        drop(conn);
+        self.evict_if_needed()?;
+        Ok(())
+    }
+
+    /// Embed `query` and return the `k` most similar stored events, ranked
+    /// by cosine similarity (a dot product, since vectors are normalized).
+    /// Keeps only a size-`k` min-heap while scanning so memory stays
+    /// bounded regardless of table size.
+    pub fn find_similar(&self, query: &str, k: usize) -> Vec<ClipboardEvent> {
+        self.scored_matches(query, k)
+            .into_iter()
+            .map(|scored| scored.event)
+            .collect()
+    }
+
+    /// Same ranking as `find_similar`, but keeps each match's score and
+    /// `content_hash` around so a caller (e.g. `ContentAnalyzer::ask_history`)
+    /// can drop low-relevance matches and cite which entries it used.
+    pub fn find_similar_scored(&self, query: &str, k: usize) -> Vec<HistoryMatch> {
+        self.scored_matches(query, k)
+            .into_iter()
+            .map(|scored| HistoryMatch {
+                entry_id: scored.content_hash,
+                score: scored.score,
+                event: scored.event,
+            })
+            .collect()
+    }
+
+    fn scored_matches(&self, query: &str, k: usize) -> Vec<Scored> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let query_vector = self.embedder.embed(query);
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = match conn.prepare(
+            "SELECT content_hash, content, content_type, source_app, timestamp, embedding FROM clipboard_history",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                warn!("find_similar: failed to prepare query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map([], |row| {
+            let content_hash: String = row.get(0)?;
+            let content: String = row.get(1)?;
+            let content_type: String = row.get(2)?;
+            let source_app: Option<String> = row.get(3)?;
+            let timestamp: String = row.get(4)?;
+            let embedding: String = row.get(5)?;
+            Ok((content_hash, content, content_type, source_app, timestamp, embedding))
+        });
+
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("find_similar: failed to run query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut heap: BinaryHeap<Scored> = BinaryHeap::new();
+
+        for row in rows.flatten() {
+            let (content_hash, content, content_type, source_app, timestamp, embedding_json) = row;
+
+            let embedding: Vec<f32> = match serde_json::from_str(&embedding_json) {
+                Ok(v) => v,
+                Err(e) => {
+                    debug!("find_similar: skipping row with bad embedding: {}", e);
+                    continue;
+                }
+            };
+
+            let score = dot(&query_vector, &embedding);
+            let event = ClipboardEvent::new(
+                content,
+                parse_content_type(&content_type),
+                source_app,
+            );
+
+            let scored = Scored {
+                score,
+                content_hash,
+                timestamp,
+                event,
+            };
+
+            if heap.len() < k {
+                heap.push(scored);
+            } else if let Some(lowest) = heap.peek() {
+                if scored.score > lowest.score {
+                    heap.pop();
+                    heap.push(scored);
+                }
+            }
+        }
+
+        let mut touched_hashes = Vec::with_capacity(heap.len());
+        let mut results: Vec<Scored> = heap.into_vec();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        for scored in &results {
+            touched_hashes.push(scored.content_hash.clone());
+        }
+        drop(conn);
+        self.touch(&touched_hashes);
+
+        results
+    }
+
+    /// Bump `last_accessed` for rows that were just returned by
+    /// `find_similar`, so genuinely-useful history survives LRU eviction
+    /// longer than rows nobody ever recalls.
+    fn touch(&self, content_hashes: &[String]) {
+        if content_hashes.is_empty() {
+            return;
+        }
+        let now = now_unix();
+        let conn = self.conn.lock().unwrap();
+        for hash in content_hashes {
+            let _ = conn.execute(
+                "UPDATE clipboard_history SET last_accessed = ?1 WHERE content_hash = ?2",
+                params![now, hash],
+            );
+        }
+    }
+
+    /// Evict the least-recently-accessed rows once the table grows past
+    /// `max_entries`.
+    fn evict_if_needed(&self) -> Result<(), ClipboardError> {
+        let conn = self.conn.lock().unwrap();
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM clipboard_history", [], |row| row.get(0))
+            .map_err(|e| ClipboardError::AccessError(format!("Failed to count history rows: {}", e)))?;
+
+        let excess = count - self.max_entries as i64;
+        if excess <= 0 {
+            return Ok(());
+        }
+
+        conn.execute(
+            "DELETE FROM clipboard_history WHERE id IN (
+                SELECT id FROM clipboard_history ORDER BY last_accessed ASC LIMIT ?1
+            )",
+            params![excess],
+        )
+        .map_err(|e| ClipboardError::AccessError(format!("Failed to evict history rows: {}", e)))?;
+
+        debug!("Evicted {} least-recently-used history rows", excess);
+        Ok(())
+    }
+}
+
+struct Scored {
+    score: f32,
+    content_hash: String,
+    #[allow(dead_code)]
+    timestamp: String,
+    event: ClipboardEvent,
+}
+
+/// A `find_similar_scored` match: the event, its cosine-similarity score,
+/// and the stable `content_hash` an `AiEngine::answer_with_sources` caller
+/// can cite back to and the UI can link back to.
+pub struct HistoryMatch {
+    pub entry_id: String,
+    pub score: f32,
+    pub event: ClipboardEvent,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    // Reversed vs. natural score order, so the max-heap `BinaryHeap` acts
+    // as a min-heap on `score` - the lowest-scoring entry is always the
+    // one `peek()`/`pop()` surface, ready to be evicted for a better match.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.score.total_cmp(&self.score)
+    }
+}
+
+fn parse_content_type(raw: &str) -> BasicContentType {
+    match raw {
+        "Url" => BasicContentType::Url,
+        "Email" => BasicContentType::Email,
+        "Phone" => BasicContentType::Phone,
+        "Financial" => BasicContentType::Financial,
+        "DateTime" => BasicContentType::DateTime,
+        "Code" => BasicContentType::Code,
+        "Address" => BasicContentType::Address,
+        _ => BasicContentType::PlainText,
+    }
+}
+
+fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::embedder::HashEmbedder;
+
+    fn temp_store() -> ClipboardHistoryStore {
+        let path = format!(
+            "{}/clipmind_history_test_{}.db",
+            std::env::temp_dir().display(),
+            uuid::Uuid::new_v4()
+        );
+        ClipboardHistoryStore::open(&path, Arc::new(HashEmbedder::default()), 5).unwrap()
+    }
+
+    #[test]
+    fn test_record_and_find_similar() {
+        let store = temp_store();
+        let event = ClipboardEvent::new(
+            "please translate this paragraph".to_string(),
+            BasicContentType::PlainText,
+            None,
+        );
+        store.record(&event).unwrap();
+
+        let results = store.find_similar("please translate this paragraph", 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].content, event.content);
+    }
+
+    #[test]
+    fn test_find_similar_scored_returns_entry_id_and_score() {
+        let store = temp_store();
+        let event = ClipboardEvent::new(
+            "please translate this paragraph".to_string(),
+            BasicContentType::PlainText,
+            None,
+        );
+        store.record(&event).unwrap();
+
+        let results = store.find_similar_scored("please translate this paragraph", 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry_id, event.content_hash);
+        assert!((results[0].score - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_dedupes_on_content_hash() {
+        let store = temp_store();
+        let event = ClipboardEvent::new("same text".to_string(), BasicContentType::PlainText, None);
+        store.record(&event).unwrap();
+        store.record(&event).unwrap();
+
+        let conn = store.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM clipboard_history", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_lru_eviction_caps_table_size() {
+        let store = ClipboardHistoryStore::open(
+            &format!(
+                "{}/clipmind_history_test_{}.db",
+                std::env::temp_dir().display(),
+                uuid::Uuid::new_v4()
+            ),
+            Arc::new(HashEmbedder::default()),
+            2,
+        )
+        .unwrap();
+
+        for i in 0..5 {
+            let event = ClipboardEvent::new(format!("entry number {}", i), BasicContentType::PlainText, None);
+            store.record(&event).unwrap();
+        }
+
+        let conn = store.conn.lock().unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM clipboard_history", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+}
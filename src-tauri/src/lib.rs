@@ -1,19 +1,22 @@
 // src-tauri/src/lib.rs 
+pub mod ai;
 pub mod clipboard;
 pub mod actions;
 pub mod analyzer;
+pub mod history;
 
 use clipboard::monitor::{ClipboardMonitor, ClipboardChange};
 use clipboard::content_detector::ContentDetector;
+use history::ClipboardItemStore;
 use std::sync::{Arc, Mutex};
 use log::{info, warn, error};
 use serde::{Serialize, Deserialize};
-use std::collections::VecDeque;
 use tokio::sync::broadcast;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 // Import from modules
 use actions::popup::{show_popup_window, run_action, close_popup, resize_popup_to_content};
+use actions::command::run_command_action;
 use analyzer::content_analyzer::ContentAnalyzer;
 use clipboard::types::CompleteAnalysis;
 
@@ -26,169 +29,224 @@ pub struct ClipboardItem {
     pub timestamp: String,
     pub content_length: usize,
     pub content_preview: String,
+    /// Which selection (CLIPBOARD/PRIMARY/SECONDARY) this item came from;
+    /// see `clipboard::types::ClipboardSelection`. Always `"Clipboard"` on
+    /// platforms with only one clipboard.
+    pub selection: String,
+    /// Base64-encoded, downscaled PNG for `Image` items, so the history
+    /// list can render a preview without shipping the full-resolution
+    /// bytes to the frontend on every `get_clipboard_history` call.
+    /// `None` for text/file items.
+    pub image_thumbnail: Option<String>,
+    /// Base64-encoded full-resolution PNG, kept alongside the thumbnail so
+    /// `copy_image_to_clipboard` can write the original image back to the
+    /// clipboard rather than the downscaled preview. `None` for text/file
+    /// items.
+    pub image_full: Option<String>,
 }
 
-// Global state
-static mut CLIPBOARD_HISTORY: Option<Arc<Mutex<VecDeque<ClipboardItem>>>> = None;
-static mut CLIPBOARD_MONITOR: Option<Arc<Mutex<ClipboardMonitor>>> = None;
-static mut IS_RUNNING: Option<Arc<Mutex<bool>>> = None;
-static mut CONTENT_ANALYZER: Option<Arc<ContentAnalyzer>> = None;
+/// Cap on persisted history rows (see `ClipboardItemStore::evict_if_needed`).
+/// The old in-memory `VecDeque` capped this at 100 and lost everything on
+/// restart; a SQLite-backed store can comfortably keep far more.
+const MAX_HISTORY_SIZE: usize = 5000;
 
-const MAX_HISTORY_SIZE: usize = 100;
+/// All shared application state, registered with `app.manage(...)` in
+/// `run()` and accessed by commands via `State<'_, AppState>` instead of
+/// through `unsafe` statics. `history`/`analyzer` are set up once at startup
+/// and never torn down; `monitor`/`sync` start `None` and are populated by
+/// `start_clipboard_monitoring`/`start_clipboard_sync` respectively.
+pub struct AppState {
+    /// `None` if the on-disk item store couldn't be opened (disk full,
+    /// permissions, corrupt file); history-backed commands degrade to
+    /// empty results/no-ops instead of the app failing to start.
+    pub history: Option<Arc<ClipboardItemStore>>,
+    pub analyzer: Arc<ContentAnalyzer>,
+    pub is_running: Arc<Mutex<bool>>,
+    pub monitor: Mutex<Option<ClipboardMonitor>>,
+    pub sync: Mutex<Option<Arc<clipboard::sync::SyncState>>>,
+}
+
+impl AppState {
+    fn new() -> Self {
+        let db_path = std::env::var("CLIPMIND_HISTORY_DB")
+            .unwrap_or_else(|_| "clipmind_items.db".to_string());
+        let history = match ClipboardItemStore::open(&db_path, MAX_HISTORY_SIZE) {
+            Ok(store) => Some(Arc::new(store)),
+            Err(e) => {
+                warn!("Failed to open clipboard item store ({}), continuing without history: {}", db_path, e);
+                None
+            }
+        };
+
+        Self {
+            history,
+            analyzer: Arc::new(ContentAnalyzer::new()),
+            is_running: Arc::new(Mutex::new(false)),
+            monitor: Mutex::new(None),
+            sync: Mutex::new(None),
+        }
+    }
+}
 
 // Safe string truncate function
 fn safe_truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         return s.to_string();
     }
-    
+
     let mut end = max_len;
     while end > 0 && !s.is_char_boundary(end) {
         end -= 1;
     }
-    
+
     if end == 0 {
         return String::new();
     }
-    
-    format!("{}...", &s[..end])
-}
 
-// Initialize global state
-fn init_global_state() {
-    unsafe {
-        if CLIPBOARD_HISTORY.is_none() {
-            CLIPBOARD_HISTORY = Some(Arc::new(Mutex::new(VecDeque::new())));
-        }
-        if IS_RUNNING.is_none() {
-            IS_RUNNING = Some(Arc::new(Mutex::new(false)));
-        }
-        if CONTENT_ANALYZER.is_none() {
-            CONTENT_ANALYZER = Some(Arc::new(ContentAnalyzer::new()));
-        }
-    }
+    format!("{}...", &s[..end])
 }
 
 // Frontend API commands
 #[tauri::command]
-async fn start_clipboard_monitoring(app: AppHandle) -> Result<String, String> {
-    init_global_state();
-    
-    unsafe {
-        // Check if it is already running
-        if let Some(ref is_running) = IS_RUNNING {
-            let running = is_running.lock().unwrap();
-            if *running {
-                return Ok("Clipboard monitoring is already running".to_string());
-            }
-        }
-        
-        // Test AI engine connection
-        if let Some(ref analyzer) = CONTENT_ANALYZER {
-            let ai_connected = analyzer.test_ai_connection().await;
-            if ai_connected {
-                info!("AI engine is ready");
-            } else {
-                warn!("AI engine connection failed, only rule engine will be used");
-            }
+async fn start_clipboard_monitoring(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    // Check if it is already running
+    {
+        let running = state.is_running.lock().unwrap();
+        if *running {
+            return Ok("Clipboard monitoring is already running".to_string());
         }
-        
-        // Create a new monitor
-        let mut monitor = ClipboardMonitor::new(None)
-            .map_err(|e| format!("Failed to create monitor: {}", e))?;
-        
-        // Start monitoring
-        let event_receiver = monitor.start_monitoring().await
-            .map_err(|e| format!("Failed to start monitoring: {}", e))?;
-        
-        info!("Monitor started");
-        
-        // Save monitor
-        CLIPBOARD_MONITOR = Some(Arc::new(Mutex::new(monitor)));
-        
-        // Set running state
-        if let Some(ref is_running) = IS_RUNNING {
-            let mut running = is_running.lock().unwrap();
-            *running = true;
-        }
-        
-        // Start event processing task
-        start_event_processing(event_receiver, app).await;
-        
-        Ok("Monitoring with AI enabled...".to_string())
     }
+
+    // Test AI engine connection
+    let ai_connected = state.analyzer.test_ai_connection().await;
+    if ai_connected {
+        info!("AI engine is ready");
+    } else {
+        warn!("AI engine connection failed, only rule engine will be used");
+    }
+
+    // Create a new monitor
+    let mut monitor = ClipboardMonitor::new(None)
+        .map_err(|e| format!("Failed to create monitor: {}", e))?;
+
+    // Start monitoring
+    let event_receiver = monitor.start_monitoring().await
+        .map_err(|e| format!("Failed to start monitoring: {}", e))?;
+
+    info!("Monitor started");
+
+    // Drive local Ollama suggestions off the same change stream, so
+    // suggestions are computed automatically as content is copied
+    // instead of only on an explicit `get_ai_suggestions` call.
+    let ollama_client = ai::OllamaClient::new(
+        std::env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+        std::env::var("OLLAMA_MODEL").unwrap_or_else(|_| "llama3.2:1b".to_string()),
+        15_000,
+    );
+    if ollama_client.is_available().await {
+        ai::spawn_driver(ollama_client, monitor.event_sender.subscribe(), app.clone());
+    } else {
+        warn!("Ollama not reachable, skipping automatic suggestion driver");
+    }
+
+    // Save monitor
+    *state.monitor.lock().unwrap() = Some(monitor);
+
+    // Set running state
+    *state.is_running.lock().unwrap() = true;
+
+    // Start event processing task
+    start_event_processing(event_receiver, app).await;
+
+    Ok("Monitoring with AI enabled...".to_string())
 }
 
 #[tauri::command]
-async fn stop_clipboard_monitoring() -> Result<String, String> {
-    unsafe {
-        // Set stopped state
-        if let Some(ref is_running) = IS_RUNNING {
-            let mut running = is_running.lock().unwrap();
-            *running = false;
-        }
-        
-        // Stop monitor
-        if let Some(ref monitor_arc) = CLIPBOARD_MONITOR {
-            let mut monitor = monitor_arc.lock().unwrap();
-            
-            if let Err(e) = monitor.stop_monitoring_sync() {
-                return Err(format!("Failed to stop monitoring: {}", e));
-            }
+async fn stop_clipboard_monitoring(state: State<'_, AppState>) -> Result<String, String> {
+    // Set stopped state
+    *state.is_running.lock().unwrap() = false;
+
+    // Stop monitor
+    if let Some(ref mut monitor) = *state.monitor.lock().unwrap() {
+        if let Err(e) = monitor.stop_monitoring_sync() {
+            return Err(format!("Failed to stop monitoring: {}", e));
         }
-        
-        // Clean up state
-        CLIPBOARD_MONITOR = None;
-        
-        Ok("Stop monitoring...".to_string())
     }
+
+    // Clean up state
+    *state.monitor.lock().unwrap() = None;
+
+    Ok("Stop monitoring...".to_string())
 }
 
 // Start event processing
 async fn start_event_processing(mut event_receiver: broadcast::Receiver<ClipboardChange>, app: AppHandle) {
-    unsafe {
-        if let Some(ref is_running_arc) = IS_RUNNING {
-            let is_running_arc = Arc::clone(is_running_arc);
-            
-            tokio::spawn(async move {
-                info!("Clipboard event processor started (AI enhanced)");
-                
-                loop {
-                    // Check if it should stop
-                    {
-                        let running = is_running_arc.lock().unwrap();
-                        if !*running {
-                            info!("Received stop signal, ending event processing");
-                            break;
-                        }
-                    }
-                    
-                    // Receive event
-                    match event_receiver.recv().await {
-                        Ok(change) => {
-                            handle_clipboard_change_with_ai(change, app.clone()).await;
-                        },
-                        Err(broadcast::error::RecvError::Closed) => {
-                            info!("Event channel closed");
-                            break;
-                        },
-                        Err(broadcast::error::RecvError::Lagged(count)) => {
-                            info!("Event processing delayed, skipped {} events", count);
-                            continue;
-                        }
-                    }
+    let is_running_arc = Arc::clone(&app.state::<AppState>().is_running);
+
+    tokio::spawn(async move {
+        info!("Clipboard event processor started (AI enhanced)");
+
+        loop {
+            // Check if it should stop
+            {
+                let running = is_running_arc.lock().unwrap();
+                if !*running {
+                    info!("Received stop signal, ending event processing");
+                    break;
                 }
-                
-                info!("Clipboard event processor stopped");
-            });
+            }
+
+            // Receive event
+            match event_receiver.recv().await {
+                Ok(change) => {
+                    handle_clipboard_change_with_ai(change, app.clone()).await;
+                },
+                Err(broadcast::error::RecvError::Closed) => {
+                    info!("Event channel closed");
+                    break;
+                },
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    info!("Event processing delayed, skipped {} events", count);
+                    continue;
+                }
+            }
         }
-    }
+
+        info!("Clipboard event processor stopped");
+    });
+}
+
+/// Downscaled thumbnail dimension (longest side, in pixels) stored in
+/// history alongside the full-resolution image; keeps `get_clipboard_history`
+/// responses small even when the clipboard holds a multi-megapixel screenshot.
+const IMAGE_THUMBNAIL_MAX_DIM: u32 = 256;
+
+/// Re-encodes a captured PNG as a downscaled PNG thumbnail, base64-encoded
+/// so it can ride along on `ClipboardItem` like every other field. Returns
+/// `None` if the bytes can't be decoded (shouldn't happen for payloads the
+/// monitor itself just encoded, but this is display-only, so it's not worth
+/// propagating as an error).
+fn encode_image_thumbnail(png_bytes: &[u8]) -> Option<String> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    let thumbnail = image::load_from_memory(png_bytes)
+        .ok()?
+        .thumbnail(IMAGE_THUMBNAIL_MAX_DIM, IMAGE_THUMBNAIL_MAX_DIM);
+
+    let mut buf = Vec::new();
+    thumbnail
+        .write_to(&mut std::io::Cursor::new(&mut buf), image::ImageFormat::Png)
+        .ok()?;
+    Some(STANDARD.encode(buf))
 }
 
 // Handle clipboard change (AI enhanced - using original popup)
 async fn handle_clipboard_change_with_ai(change: ClipboardChange, app: AppHandle) {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use clipboard::types::ClipboardPayload;
+
     let content_preview = safe_truncate(&change.event.content, 50);
-    
+
     info!(
         "Clipboard change - delay: {}ms, type: {:?}, length: {}, preview: '{}'",
         change.source_detection_time_ms,
@@ -196,7 +254,14 @@ async fn handle_clipboard_change_with_ai(change: ClipboardChange, app: AppHandle
         change.event.content_length,
         content_preview
     );
-    
+
+    let (image_thumbnail, image_full) = match &change.event.payload {
+        ClipboardPayload::Image { png_bytes, .. } => {
+            (encode_image_thumbnail(png_bytes), Some(STANDARD.encode(png_bytes)))
+        }
+        _ => (None, None),
+    };
+
     // Add to history
     let clipboard_item = ClipboardItem {
         id: uuid::Uuid::new_v4().to_string(),
@@ -209,20 +274,17 @@ async fn handle_clipboard_change_with_ai(change: ClipboardChange, app: AppHandle
         } else {
             change.event.content.clone()
         },
+        selection: format!("{:?}", change.event.selection),
+        image_thumbnail,
+        image_full,
     };
     
-    unsafe {
-        if let Some(ref history) = CLIPBOARD_HISTORY {
-            let mut history_guard = history.lock().unwrap();
-            history_guard.push_front(clipboard_item);
-            
-            if history_guard.len() > MAX_HISTORY_SIZE {
-                history_guard.pop_back();
-            }
+    if let Some(history) = app.state::<AppState>().history.as_ref() {
+        if let Err(e) = history.insert(&clipboard_item) {
+            warn!("Failed to persist clipboard item: {}", e);
         }
     }
-    
-    
+
     // Use show_popup_window but pass AI analysis result
     if let Err(e) = show_popup_window(
         app,
@@ -234,167 +296,621 @@ async fn handle_clipboard_change_with_ai(change: ClipboardChange, app: AppHandle
 }
 
 
+/// Wraps `compute_ai_suggestions` with a cache keyed by `item_id` (the
+/// clipboard history row's id), so re-showing the popup for the same clip -
+/// e.g. the user re-opens it from history - doesn't re-run rule/AI analysis.
+/// `item_id` is optional since callers without a persisted item (e.g. ad
+/// hoc content passed straight from `test_clipboard_detection`) just skip
+/// caching.
 #[tauri::command]
 async fn get_ai_suggestions(
     content: String,
     content_type: String,
+    item_id: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<Vec<serde_json::Value>, String> {
-    info!("Start real AI analysis, type: {}", content_type);
-    
-    init_global_state();
-    
-    unsafe {
-        if let Some(ref analyzer) = CONTENT_ANALYZER {
-            // Parse content type
-            let parsed_type = match content_type.as_str() {
-                "Url" => clipboard::types::BasicContentType::Url,
-                "Email" => clipboard::types::BasicContentType::Email,
-                "Phone" => clipboard::types::BasicContentType::Phone,
-                "Financial" => clipboard::types::BasicContentType::Financial,
-                "Code" => clipboard::types::BasicContentType::Code,
-                "Address" => clipboard::types::BasicContentType::Address,
-                "DateTime" => clipboard::types::BasicContentType::DateTime,
-                _ => clipboard::types::BasicContentType::PlainText,
-            };
-            
-            // Directly call ai_engine's predict_intent method
-            match analyzer.ai_engine.predict_intent(&content, &parsed_type).await {
-                Ok(predictions) => {
-                    // Convert to frontend format
-                    let ai_suggestions: Vec<serde_json::Value> = predictions
-                        .iter()
-                        .enumerate()
-                        .map(|(index, action)| serde_json::json!({
-                            "id": action.action_id,
-                            "label": action.label,
-                            "icon": action.icon,
-                            "hotkey": (index + 4).to_string(), // start from 4th
-                            "source": "ai",
-                            "reason": action.reason,
-                            "confidence": action.confidence
-                        }))
-                        .collect();
-                    
-                    info!("Real AI suggestions generated: {} suggestions", ai_suggestions.len());
-                    Ok(ai_suggestions)
-                },
-                Err(e) => {
-                    warn!("AI analysis failed: {}", e);
-                    
-                    // Provide smart fallback suggestions
-                    let fallback_suggestions = match content_type.as_str() {
-                        "Url" => vec![
-                            serde_json::json!({
-                                "id": "ai_summarize_webpage",
-                                "label": "AI Summarize Webpage",
-                                "icon": "üìñ",
-                                "hotkey": "4",
-                                "source": "ai",
-                                "reason": "Fallback smart suggestion",
-                                "confidence": 0.6
-                            })
-                        ],
-                        "Code" => vec![
-                            serde_json::json!({
-                                "id": "ai_explain_code",
-                                "label": "AI Explain Code", 
-                                "icon": "üí°",
-                                "hotkey": "4",
-                                "source": "ai",
-                                "reason": "Fallback smart suggestion",
-                                "confidence": 0.7
-                            })
-                        ],
-                        _ => vec![
-                            serde_json::json!({
-                                "id": "ai_translate",
-                                "label": "AI Translate",
-                                "icon": "üìù", 
-                                "hotkey": "4",
-                                "source": "ai",
-                                "reason": "Fallback smart suggestion",
-                                "confidence": 0.6
-                            })
-                        ]
-                    };
-                    
-                    info!("Using fallback smart suggestions: {}", fallback_suggestions.len());
-                    Ok(fallback_suggestions)
+    if let Some(id) = item_id.as_deref() {
+        let cached = state.history.as_ref().and_then(|history| history.cached_analysis(id));
+        if let Some(cached_json) = cached {
+            match serde_json::from_str::<Vec<serde_json::Value>>(&cached_json) {
+                Ok(suggestions) => {
+                    info!("Using cached AI suggestions for item {}", id);
+                    return Ok(suggestions);
                 }
+                Err(e) => warn!("Failed to parse cached AI suggestions for item {}: {}", id, e),
             }
-        } else {
-            Err("AI engine not initialized".to_string())
+        }
+    }
+
+    let suggestions = compute_ai_suggestions(content, content_type, &state.analyzer).await?;
+
+    if let (Some(id), Some(history)) = (item_id.as_deref(), state.history.as_ref()) {
+        match serde_json::to_string(&suggestions) {
+            Ok(json) => {
+                if let Err(e) = history.record_analysis(id, &json) {
+                    warn!("Failed to cache AI suggestions for item {}: {}", id, e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize AI suggestions for caching: {}", e),
+        }
+    }
+
+    Ok(suggestions)
+}
+
+async fn compute_ai_suggestions(
+    content: String,
+    content_type: String,
+    analyzer: &ContentAnalyzer,
+) -> Result<Vec<serde_json::Value>, String> {
+    info!("Start real AI analysis, type: {}", content_type);
+
+    // Images aren't something `AiEngine::predict_intent` can reason about
+    // the way it does URLs/code/etc - it's a text-only chat-completion
+    // client, and `content` here is only the `"[image WxH, N bytes]"`
+    // preview - so route to the OCR/vision task instead of the intent
+    // predictor below.
+    if content_type == "Image" {
+        return get_image_ai_suggestions(content, analyzer).await;
+    }
+
+    // Lets the frontend warn when a clip is long enough that its prompt
+    // will have been truncated (see `AiEngine::task_token_budget`) before
+    // the model ever sees all of it.
+    let estimated_tokens = ai::tokenizer::estimate_tokens(&content);
+
+    // Parse content type
+    let parsed_type = match content_type.as_str() {
+        "Url" => clipboard::types::BasicContentType::Url,
+        "Email" => clipboard::types::BasicContentType::Email,
+        "Phone" => clipboard::types::BasicContentType::Phone,
+        "Financial" => clipboard::types::BasicContentType::Financial,
+        "Code" => clipboard::types::BasicContentType::Code,
+        "Address" => clipboard::types::BasicContentType::Address,
+        "DateTime" => clipboard::types::BasicContentType::DateTime,
+        _ => clipboard::types::BasicContentType::PlainText,
+    };
+
+    // Directly call ai_engine's predict_intent method
+    match analyzer.ai_engine.predict_intent(&content, &parsed_type).await {
+        Ok(predictions) => {
+            // Convert to frontend format
+            let ai_suggestions: Vec<serde_json::Value> = predictions
+                .iter()
+                .enumerate()
+                .map(|(index, action)| serde_json::json!({
+                    "id": action.action_id,
+                    "label": action.label,
+                    "icon": action.icon,
+                    "hotkey": (index + 4).to_string(), // start from 4th
+                    "source": "ai",
+                    "reason": action.reason,
+                    "confidence": action.confidence,
+                    "estimated_tokens": estimated_tokens
+                }))
+                .collect();
+
+            info!("Real AI suggestions generated: {} suggestions", ai_suggestions.len());
+            Ok(ai_suggestions)
+        },
+        Err(e) => {
+            warn!("AI analysis failed: {}", e);
+
+            // Provide smart fallback suggestions
+            let fallback_suggestions = match content_type.as_str() {
+                "Url" => vec![
+                    serde_json::json!({
+                        "id": "ai_summarize_webpage",
+                        "label": "AI Summarize Webpage",
+                        "icon": "üìñ",
+                        "hotkey": "4",
+                        "source": "ai",
+                        "reason": "Fallback smart suggestion",
+                        "confidence": 0.6,
+                        "estimated_tokens": estimated_tokens
+                    })
+                ],
+                "Code" => vec![
+                    serde_json::json!({
+                        "id": "ai_explain_code",
+                        "label": "AI Explain Code",
+                        "icon": "üí°",
+                        "hotkey": "4",
+                        "source": "ai",
+                        "reason": "Fallback smart suggestion",
+                        "confidence": 0.7,
+                        "estimated_tokens": estimated_tokens
+                    })
+                ],
+                _ => vec![
+                    serde_json::json!({
+                        "id": "ai_translate",
+                        "label": "AI Translate",
+                        "icon": "üìù",
+                        "hotkey": "4",
+                        "source": "ai",
+                        "reason": "Fallback smart suggestion",
+                        "confidence": 0.6,
+                        "estimated_tokens": estimated_tokens
+                    })
+                ]
+            };
+
+            info!("Using fallback smart suggestions: {}", fallback_suggestions.len());
+            Ok(fallback_suggestions)
         }
     }
 }
 
+/// `get_ai_suggestions`'s `Image` branch. `content` is the `"[image WxH, N
+/// bytes]"` preview (see `ClipboardPayload::text_preview`) - there's no
+/// pixel data to run `predict_intent` over, so this runs the dedicated
+/// `ocr_image` task (see `AiEngine::build_task_prompt`) instead and wraps
+/// its single suggestion in the same shape the frontend expects from
+/// `get_ai_suggestions`.
+async fn get_image_ai_suggestions(content: String, analyzer: &ContentAnalyzer) -> Result<Vec<serde_json::Value>, String> {
+    let estimated_tokens = ai::tokenizer::estimate_tokens(&content);
+
+    match analyzer.ai_engine.process_ai_task(&content, "ocr_image", None).await {
+        Ok(suggestion) => Ok(vec![serde_json::json!({
+            "id": "ai_image_suggestion",
+            "label": suggestion,
+            "icon": "🔎",
+            "hotkey": "4",
+            "source": "ai",
+            "reason": "AI analysis",
+            "confidence": 0.6,
+            "estimated_tokens": estimated_tokens
+        })]),
+        Err(e) => {
+            warn!("Image AI analysis failed: {}", e);
+            Ok(vec![serde_json::json!({
+                "id": "ocr_image",
+                "label": "Extract Text (OCR)",
+                "icon": "🔎",
+                "hotkey": "4",
+                "source": "ai",
+                "reason": "Fallback smart suggestion",
+                "confidence": 0.5,
+                "estimated_tokens": estimated_tokens
+            })])
+        }
+    }
+}
+
+
+/// Event carrying one incremental piece of streamed suggestion text.
+const STREAM_SUGGESTION_CHUNK_EVENT: &str = "ai-suggestion-chunk";
+/// Event carrying the final `AiAnalysis` once streaming completes.
+const STREAM_SUGGESTIONS_DONE_EVENT: &str = "ai-suggestions-done";
+
+/// Streaming counterpart to `get_ai_suggestions`: picks an `AiProvider` at
+/// runtime (see `ai::select_provider`), truncates the content to the
+/// provider's token budget, and emits partial text to the popup as it's
+/// generated instead of blocking until the whole analysis is done.
+#[tauri::command]
+async fn stream_ai_suggestions(
+    app: AppHandle,
+    content: String,
+    content_type: String,
+) -> Result<(), String> {
+    info!("Start streaming AI analysis, type: {}", content_type);
+
+    let parsed_type = match content_type.as_str() {
+        "Url" => clipboard::types::BasicContentType::Url,
+        "Email" => clipboard::types::BasicContentType::Email,
+        "Phone" => clipboard::types::BasicContentType::Phone,
+        "Financial" => clipboard::types::BasicContentType::Financial,
+        "Code" => clipboard::types::BasicContentType::Code,
+        "Address" => clipboard::types::BasicContentType::Address,
+        "DateTime" => clipboard::types::BasicContentType::DateTime,
+        _ => clipboard::types::BasicContentType::PlainText,
+    };
+
+    // Secrets never leave the machine: send the redacted form to the
+    // (possibly remote) provider when the content scans as sensitive.
+    let redaction = analyzer::Redactor::new().scan(&content);
+    let safe_content = redaction.redacted_content.unwrap_or(content);
+
+    let request = clipboard::types::IntentPredictionRequest {
+        content: safe_content,
+        content_type: parsed_type,
+        context: None,
+    };
+
+    let provider = ai::select_provider();
+    let emit_app = app.clone();
+    let mut on_token = move |chunk: &str| {
+        let _ = emit_app.emit(STREAM_SUGGESTION_CHUNK_EVENT, chunk.to_string());
+    };
+
+    match provider.predict_intents_stream(&request, &mut on_token).await {
+        Ok(analysis) => {
+            let _ = app.emit(STREAM_SUGGESTIONS_DONE_EVENT, analysis);
+            Ok(())
+        }
+        Err(e) => {
+            warn!("Streaming AI analysis failed: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
 
 #[tauri::command]
 async fn process_ai_task(
     task_type: String,
     content: String,
-    parameters: Option<std::collections::HashMap<String, String>>
+    parameters: Option<std::collections::HashMap<String, String>>,
+    state: State<'_, AppState>,
 ) -> Result<String, String> {
     info!("Processing AI task: {}", task_type);
-    
-    unsafe {
-        if let Some(ref analyzer) = CONTENT_ANALYZER {
-            match analyzer.process_ai_task(&content, &task_type, parameters).await {
-                Ok(result) => {
-                    info!("AI task completed: {}", task_type);
-                    Ok(result)
-                },
-                Err(e) => {
-                    error!("AI task failed: {}", e);
-                    Err(e)
-                }
+
+    match state.analyzer.process_ai_task(&content, &task_type, parameters).await {
+        Ok(result) => {
+            info!("AI task completed: {}", task_type);
+            Ok(result)
+        },
+        Err(e) => {
+            error!("AI task failed: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Event carrying one incremental piece of a streamed `process_ai_task`.
+const TASK_STREAM_CHUNK_EVENT: &str = "ai-task-chunk";
+/// Event carrying the final result (`Ok(full_text)` or `Err(message)`)
+/// once a streamed `process_ai_task` finishes.
+const TASK_STREAM_DONE_EVENT: &str = "ai-task-done";
+
+/// Streaming counterpart to `process_ai_task`: emits each chunk as
+/// `ai-task-chunk` as soon as the model generates it, then `ai-task-done`
+/// with the assembled result, so long summaries/translations show up
+/// incrementally instead of only once fully generated.
+#[tauri::command]
+async fn process_ai_task_stream(
+    app: AppHandle,
+    task_type: String,
+    content: String,
+    parameters: Option<std::collections::HashMap<String, String>>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!("Processing AI task (streaming): {}", task_type);
+
+    let mut stream = match state.analyzer.process_ai_task_stream(&content, &task_type, parameters).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            error!("AI task stream failed to start: {}", e);
+            let _ = app.emit(TASK_STREAM_DONE_EVENT, Err::<String, _>(e.clone()));
+            return Err(e);
+        }
+    };
+
+    use futures_util::StreamExt;
+    let mut full = String::new();
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            Ok(piece) => {
+                full.push_str(&piece);
+                let _ = app.emit(TASK_STREAM_CHUNK_EVENT, piece);
+            }
+            Err(e) => {
+                error!("AI task stream failed: {}", e);
+                let _ = app.emit(TASK_STREAM_DONE_EVENT, Err::<String, _>(e.clone()));
+                return Err(e);
             }
-        } else {
-            Err("AI engine not initialized".to_string())
         }
     }
+
+    info!("AI task stream completed: {}", task_type);
+    let _ = app.emit(TASK_STREAM_DONE_EVENT, Ok::<_, String>(full));
+    Ok(())
 }
 
-// Get clipboard history
+/// Chains several AI tasks together (e.g. `[{"task_type":"summarize"},
+/// {"task_type":"translate"}]`), each step's output feeding the next
+/// step's input, so the suggestion layer can compose actions the flat
+/// `process_ai_task` dispatch can't express in one call.
 #[tauri::command]
-async fn get_clipboard_history() -> Result<Vec<ClipboardItem>, String> {
-    init_global_state();
-    
-    unsafe {
-        if let Some(ref history) = CLIPBOARD_HISTORY {
-            let history_guard = history.lock().unwrap();
-            Ok(history_guard.iter().cloned().collect())
-        } else {
-            Ok(vec![])
+async fn process_ai_pipeline(
+    content: String,
+    steps: Vec<clipboard::types::PipelineStep>,
+    state: State<'_, AppState>,
+) -> Result<clipboard::types::PipelineResult, String> {
+    info!("Processing AI pipeline with {} step(s)", steps.len());
+
+    match state.analyzer.process_ai_pipeline(&content, steps).await {
+        Ok(result) => {
+            info!("AI pipeline completed, {} step output(s)", result.step_outputs.len());
+            Ok(result)
+        },
+        Err(e) => {
+            error!("AI pipeline failed: {}", e);
+            Err(e)
         }
     }
 }
 
+/// Retrieval-augmented Q&A over clipboard history: answers `query` using
+/// only the past clips judged relevant, citing which ones it used.
+#[tauri::command]
+async fn ask_clipboard_history(query: String, state: State<'_, AppState>) -> Result<clipboard::types::RagAnswer, String> {
+    info!("Answering question from clipboard history");
+
+    match state.analyzer.ask_history(&query).await {
+        Ok(result) => {
+            info!("ask_history completed, {} source(s) cited", result.cited_entry_ids.len());
+            Ok(result)
+        },
+        Err(e) => {
+            error!("ask_history failed: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Teaches the local bayes classifier that `content` belongs to `category`
+/// (e.g. "shopping", "meeting info"), so similar `PlainText` clips are
+/// recognized locally on future clips instead of needing an AI call. The
+/// frontend should call this whenever the user accepts/confirms a category
+/// for a clip, not on every clip copied.
+#[tauri::command]
+fn train_content_category(content: String, category: String, state: State<'_, AppState>) -> Result<String, String> {
+    state.analyzer.train_category(&content, &category);
+    Ok("trained".to_string())
+}
+
+/// Records that the user picked `action_id` out of the suggestions offered
+/// for a clip of `content_type`, so future `merge_suggestions` calls for
+/// that content type rank it higher. The frontend should call this when the
+/// user actually clicks/executes a suggested action, not just when it's shown.
+#[tauri::command]
+fn record_action_chosen(content_type: String, action_id: String, state: State<'_, AppState>) -> Result<String, String> {
+    let parsed_type = match content_type.as_str() {
+        "Url" => clipboard::types::BasicContentType::Url,
+        "Email" => clipboard::types::BasicContentType::Email,
+        "Phone" => clipboard::types::BasicContentType::Phone,
+        "Financial" => clipboard::types::BasicContentType::Financial,
+        "Code" => clipboard::types::BasicContentType::Code,
+        "Address" => clipboard::types::BasicContentType::Address,
+        "DateTime" => clipboard::types::BasicContentType::DateTime,
+        _ => clipboard::types::BasicContentType::PlainText,
+    };
+
+    state.analyzer.record_action_chosen(&parsed_type, &action_id);
+    Ok("recorded".to_string())
+}
+
+/// Runs `process_ai_task` over many `(content, task_type)` pairs
+/// concurrently (e.g. translating every item in a multi-select at once)
+/// instead of one round trip at a time. Each item's result/error is
+/// returned in the same order as `items`.
+#[tauri::command]
+async fn process_ai_tasks_batch(
+    items: Vec<(String, String)>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Result<String, String>>, String> {
+    info!("Processing AI task batch, {} item(s)", items.len());
+
+    let results = state.analyzer.process_ai_tasks_batch(items).await;
+    info!("AI task batch completed, {} result(s)", results.len());
+    Ok(results)
+}
+
+/// Multi-step tool-calling over a piece of content (e.g. auto-fetching a
+/// URL then summarizing it) instead of discrete manual `process_ai_task` calls.
+#[tauri::command]
+async fn run_agentic_task(
+    content: String,
+    basic_type: clipboard::types::BasicContentType,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    info!("Running agentic task, content type: {:?}", basic_type);
+
+    match state.analyzer.run_agentic_task(&content, &basic_type).await {
+        Ok(result) => {
+            info!("Agentic task completed");
+            Ok(result)
+        },
+        Err(e) => {
+            error!("Agentic task failed: {}", e);
+            Err(e)
+        }
+    }
+}
+
+// Get clipboard history
+#[tauri::command]
+async fn get_clipboard_history(state: State<'_, AppState>) -> Result<Vec<ClipboardItem>, String> {
+    Ok(state.history.as_ref().map(|h| h.list(MAX_HISTORY_SIZE)).unwrap_or_default())
+}
+
+/// Substring content match and/or `content_type` filter over the
+/// persistent history (see `ClipboardItemStore::search`), most recently
+/// copied first. `query`/`content_type` are both optional so the frontend
+/// can use this as a plain "browse by type" list too.
+#[tauri::command]
+async fn search_clipboard_history(
+    query: Option<String>,
+    content_type: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ClipboardItem>, String> {
+    Ok(state
+        .history
+        .as_ref()
+        .map(|h| h.search(query.as_deref(), content_type.as_deref(), limit.unwrap_or(50)))
+        .unwrap_or_default())
+}
+
 // Clear history
 #[tauri::command]
-async fn clear_clipboard_history() -> Result<String, String> {
-    unsafe {
-        if let Some(ref history) = CLIPBOARD_HISTORY {
-            let mut history_guard = history.lock().unwrap();
-            history_guard.clear();
+async fn clear_clipboard_history(state: State<'_, AppState>) -> Result<String, String> {
+    match state.history.as_ref() {
+        Some(history) => {
+            history.clear().map_err(|e| e.to_string())?;
             Ok("Clipboard history cleared".to_string())
-        } else {
-            Ok("No history to clear".to_string())
         }
+        None => Ok("Clipboard history is unavailable".to_string()),
     }
 }
 
-// Copy specific item to clipboard
+// Copy specific item to clipboard. `selection` picks which X11/Wayland
+// selection to write into ("Clipboard"/"Primary"/"Secondary", defaulting to
+// "Clipboard"); on Windows/macOS there's only one clipboard, so Primary and
+// Secondary silently fall back to it (see `write_text_for_selection`).
 #[tauri::command]
-async fn copy_item_to_clipboard(content: String) -> Result<String, String> {
+async fn copy_item_to_clipboard(content: String, selection: Option<String>) -> Result<String, String> {
     use arboard::Clipboard;
-    
+    use clipboard::types::ClipboardSelection;
+
+    let selection = match selection.as_deref() {
+        Some("Primary") => ClipboardSelection::Primary,
+        Some("Secondary") => ClipboardSelection::Secondary,
+        _ => ClipboardSelection::Clipboard,
+    };
+
     let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
-    clipboard.set_text(&content).map_err(|e| e.to_string())?;
-    
+    clipboard::monitor::write_text_for_selection(&mut clipboard, selection, &content)
+        .map_err(|e| e.to_string())?;
+
     Ok("Copied to clipboard".to_string())
 }
 
+/// Copies a history item's full-resolution image back to the system
+/// clipboard. Unlike `copy_item_to_clipboard`, this always targets the
+/// regular `Clipboard` selection - `PRIMARY`/`SECONDARY` are highlight-to-
+/// copy text selections and arboard has no image equivalent for them.
+#[tauri::command]
+async fn copy_image_to_clipboard(id: String, state: State<'_, AppState>) -> Result<String, String> {
+    use arboard::{Clipboard, ImageData};
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    let image_full = state
+        .history
+        .as_ref()
+        .ok_or_else(|| "Clipboard history is unavailable".to_string())?
+        .find_by_id(&id)
+        .and_then(|item| item.image_full)
+        .ok_or_else(|| format!("No image found for history item {}", id))?;
+
+    let png_bytes = STANDARD.decode(&image_full).map_err(|e| e.to_string())?;
+    let decoded = image::load_from_memory(&png_bytes)
+        .map_err(|e| e.to_string())?
+        .to_rgba8();
+    let (width, height) = (decoded.width() as usize, decoded.height() as usize);
+
+    let image_data = ImageData {
+        width,
+        height,
+        bytes: std::borrow::Cow::Owned(decoded.into_raw()),
+    };
+
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard.set_image(image_data).map_err(|e| e.to_string())?;
+
+    Ok("Image copied to clipboard".to_string())
+}
+
+fn clipboard_item_to_synced(item: &ClipboardItem, origin_device: Option<String>) -> clipboard::sync::SyncedItem {
+    clipboard::sync::SyncedItem {
+        id: item.id.clone(),
+        content: item.content.clone(),
+        content_type: item.content_type.clone(),
+        timestamp: item.timestamp.clone(),
+        content_length: item.content_length,
+        content_preview: item.content_preview.clone(),
+        selection: item.selection.clone(),
+        origin_device,
+    }
+}
+
+/// `SyncedItem` has no image fields (see `clipboard::sync`'s doc comment -
+/// it only knows about the flat text-oriented shape), so pulled items are
+/// reconstructed without a thumbnail/full image even for `content_type ==
+/// "Image"`; the history entry still shows its text preview.
+fn synced_item_to_clipboard_item(item: clipboard::sync::SyncedItem) -> ClipboardItem {
+    ClipboardItem {
+        id: item.id,
+        content: item.content,
+        content_type: item.content_type,
+        timestamp: item.timestamp,
+        content_length: item.content_length,
+        image_thumbnail: None,
+        image_full: None,
+        content_preview: item.content_preview,
+        selection: item.selection,
+    }
+}
+
+/// Starts syncing local clipboard history with the relay/peer at
+/// `endpoint` (authenticated with `secret`). Pushed/pulled items are
+/// merged straight into `AppState::history`; pulled items never trigger
+/// the AI popup, since only the local monitor's `ClipboardChange` stream
+/// does that (see `handle_clipboard_change_with_ai`).
+#[tauri::command]
+async fn start_clipboard_sync(endpoint: String, secret: String, state: State<'_, AppState>) -> Result<String, String> {
+    let mut sync_slot = state.sync.lock().unwrap();
+    if sync_slot.is_some() {
+        return Ok("Clipboard sync is already running".to_string());
+    }
+
+    let history = state
+        .history
+        .clone()
+        .ok_or_else(|| "Clipboard history is unavailable, cannot start sync".to_string())?;
+    let device_id = uuid::Uuid::new_v4().to_string();
+    let config = clipboard::sync::SyncConfig::new(endpoint, secret);
+
+    let source_history = history.clone();
+    let local_source: clipboard::sync::LocalSource = Box::new(move || {
+        source_history
+            .list(MAX_HISTORY_SIZE)
+            .iter()
+            .map(|item| clipboard_item_to_synced(item, None))
+            .collect()
+    });
+
+    let sink_history = history.clone();
+    let remote_sink: clipboard::sync::RemoteSink = Box::new(move |items| {
+        // `insert_if_absent` dedupes on id itself (a unique constraint),
+        // so there's no need to scan the table first the way the old
+        // `VecDeque`'s `guard.iter().any(...)` check did.
+        for item in items {
+            sink_history.insert_if_absent(&synced_item_to_clipboard_item(item));
+        }
+    });
+
+    *sync_slot = Some(clipboard::sync::start_msg_sync(config, device_id, local_source, remote_sink));
+
+    Ok("Clipboard sync started".to_string())
+}
+
+#[tauri::command]
+async fn stop_clipboard_sync(state: State<'_, AppState>) -> Result<String, String> {
+    let mut sync_slot = state.sync.lock().unwrap();
+    if let Some(ref sync_state) = *sync_slot {
+        sync_state.stop();
+    }
+    *sync_slot = None;
+    Ok("Clipboard sync stopped".to_string())
+}
+
+#[tauri::command]
+async fn get_sync_status(state: State<'_, AppState>) -> Result<clipboard::sync::SyncStatus, String> {
+    match *state.sync.lock().unwrap() {
+        Some(ref sync_state) => Ok(sync_state.status()),
+        None => Ok(clipboard::sync::SyncStatus {
+            running: false,
+            device_id: String::new(),
+            endpoint: String::new(),
+            pushed_count: 0,
+            pulled_count: 0,
+            last_error: None,
+        }),
+    }
+}
+
 // Test command
 #[tauri::command]
 async fn test_clipboard_detection(content: String) -> Result<String, String> {
@@ -409,6 +925,7 @@ async fn test_clipboard_detection(content: String) -> Result<String, String> {
         clipboard::types::BasicContentType::DateTime => "üìÖ Date",
         clipboard::types::BasicContentType::Code => "üíª Code",
         clipboard::types::BasicContentType::Address => "üè† Address",
+        clipboard::types::BasicContentType::Image => "🖼️ Image",
         clipboard::types::BasicContentType::PlainText => "üìù PlainText",
     };
     
@@ -417,20 +934,12 @@ async fn test_clipboard_detection(content: String) -> Result<String, String> {
 
 // Test AI connection command
 #[tauri::command]
-async fn test_ai_connection() -> Result<String, String> {
-    init_global_state();
-    
-    unsafe {
-        if let Some(ref analyzer) = CONTENT_ANALYZER {
-            let connected = analyzer.test_ai_connection().await;
-            if connected {
-                Ok("AI engine connection successful".to_string())
-            } else {
-                Err("AI engine connection failed".to_string())
-            }
-        } else {
-            Err("AI engine not initialized".to_string())
-        }
+async fn test_ai_connection(state: State<'_, AppState>) -> Result<String, String> {
+    let connected = state.analyzer.test_ai_connection().await;
+    if connected {
+        Ok("AI engine connection successful".to_string())
+    } else {
+        Err("AI engine connection failed".to_string())
     }
 }
 
@@ -440,25 +949,60 @@ pub fn run() {
     log::info!("ClipMind AI Enhanced application starting...");
     
     tauri::Builder::default()
+        .setup(|app| {
+            // Scratch files for actions like `open_vscode`/`save_text` live
+            // under the app's data directory instead of the current
+            // directory, falling back to the OS temp dir if that can't be
+            // resolved (e.g. running outside a packaged app).
+            let scratch_dir = app
+                .path()
+                .app_data_dir()
+                .unwrap_or_else(|_| std::env::temp_dir())
+                .join("scratch");
+            let session_store = Arc::new(
+                actions::SessionStore::new(scratch_dir)
+                    .expect("failed to initialize session store"),
+            );
+            let action_registry = actions::ActionRegistry::with_builtins(session_store.clone());
+            action_registry.register_command_actions();
+            app.manage(action_registry);
+            app.manage(session_store);
+            app.manage(AppState::new());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Original clipboard commands
             start_clipboard_monitoring,
             stop_clipboard_monitoring,
             get_clipboard_history,
+            search_clipboard_history,
             clear_clipboard_history,
             copy_item_to_clipboard,
+            copy_image_to_clipboard,
             test_clipboard_detection,
-            
+            start_clipboard_sync,
+            stop_clipboard_sync,
+            get_sync_status,
+
             // Original popup commands
             show_popup_window,
             run_action,
+            run_command_action,
             close_popup,
             resize_popup_to_content,
             
             // AI enhanced commands
             get_ai_suggestions,
+            stream_ai_suggestions,
             process_ai_task,
+            process_ai_task_stream,
+            process_ai_pipeline,
+            process_ai_tasks_batch,
+            ask_clipboard_history,
+            run_agentic_task,
             test_ai_connection,
+            train_content_category,
+            record_action_chosen,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");